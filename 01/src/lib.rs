@@ -0,0 +1,174 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, number};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day 01
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(1, &args.inputs, args.format, None, parse, process);
+}
+
+fn process(moves: Vec<(char, isize)>, report: &mut Report) {
+    let start = Instant::now();
+    let zeros = count_zeros(&moves);
+    report.answer(2, zeros, start.elapsed());
+}
+
+/// Each line as its direction (`L` or `R`) and how many clicks to turn
+pub fn parse(data: &str) -> Result<Vec<(char, isize)>, ParseError> {
+    let mut moves = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let mut chars = line.chars();
+        let direction = match chars.next() {
+            Some(direction @ ('L' | 'R')) => direction,
+            Some(other) => return Err(ParseError::new(i + 1, ErrorKind::UnexpectedChar(other))),
+            None => return Err(ParseError::new(i + 1, ErrorKind::Missing("direction"))),
+        };
+        let distance: isize = number(i + 1, chars.as_str())?;
+        if distance < 0 {
+            let why = format!("can't turn {distance} clicks");
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        moves.push((direction, distance));
+    }
+    Ok(moves)
+}
+
+/// How many times any click leaves the dial pointing at 0
+fn count_zeros(moves: &[(char, isize)]) -> isize {
+    let mut pointer = 50_isize;
+    let mut zeros = 0;
+
+    for (direction, distance) in moves {
+        let mut extra_zeros = distance / 100;
+        let distance = distance % 100;
+        if distance == 0 {
+            // only whole turns, so the pointer ends up back where it started
+            zeros += extra_zeros;
+            continue;
+        }
+        match direction {
+            'L' => {
+                if 0 == pointer {
+                    extra_zeros -= 1;
+                }
+                pointer -= distance;
+                if pointer < 0 {
+                    extra_zeros += 1;
+                    pointer += 100;
+                }
+            }
+            'R' => {
+                pointer += distance;
+                if pointer > 99 {
+                    if pointer > 100 {
+                        extra_zeros += 1;
+                    }
+                    pointer -= 100;
+                }
+            }
+            _ => unreachable!("parse only allows L and R"),
+        }
+        zeros += extra_zeros;
+        if 0 == pointer {
+            zeros += 1;
+        }
+    }
+    zeros
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Turns the dial one click at a time
+    fn brute_force(moves: &[(char, isize)]) -> isize {
+        let mut pointer = 50;
+        let mut zeros = 0;
+        for (direction, distance) in moves {
+            let step = if *direction == 'L' { 99 } else { 1 };
+            for _ in 0..*distance {
+                pointer = (pointer + step) % 100;
+                if pointer == 0 {
+                    zeros += 1;
+                }
+            }
+        }
+        zeros
+    }
+
+    fn moves() -> impl Strategy<Value = Vec<(char, isize)>> {
+        prop::collection::vec((prop_oneof![Just('L'), Just('R')], 0..500_isize), 0..40)
+    }
+
+    #[test]
+    fn test_example() {
+        let moves = parse("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82").unwrap();
+        assert_eq!(6, count_zeros(&moves));
+    }
+
+    #[test]
+    fn test_whole_turns() {
+        // from 0, a whole turn clicks past 0 once per rotation, ending on it
+        let moves = parse("L50\nR100\nR0\nL200").unwrap();
+        assert_eq!(4, count_zeros(&moves));
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        assert_eq!(
+            ParseError::new(2, ErrorKind::UnexpectedChar('X')),
+            parse("L1\nX1").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::new(1, ErrorKind::Missing("direction")),
+            parse("\n").unwrap_err()
+        );
+        assert!(matches!(
+            parse("L").unwrap_err().kind,
+            ErrorKind::BadNumber(_)
+        ));
+        assert!(matches!(
+            parse("L-3").unwrap_err().kind,
+            ErrorKind::Invalid(_)
+        ));
+        assert!(matches!(
+            parse("é5").unwrap_err().kind,
+            ErrorKind::UnexpectedChar('é')
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(moves in moves()) {
+            prop_assert_eq!(brute_force(&moves), count_zeros(&moves));
+        }
+
+        #[test]
+        fn parses_what_it_prints(moves in moves()) {
+            let text: Vec<String> = moves.iter().map(|(d, n)| format!("{d}{n}")).collect();
+            prop_assert_eq!(Ok(moves), parse(&text.join("\n")));
+        }
+    }
+}
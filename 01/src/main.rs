@@ -1,65 +1,3 @@
-use clap::Parser;
-use std::{fs, path::PathBuf};
-
-/// AOC 25 day 01
-#[derive(Parser)]
-#[command()]
-struct Args {
-    /// Path to the input file
-    input: PathBuf,
-}
-
 fn main() {
-    let args = Args::parse();
-
-    let contents = match fs::read_to_string(&args.input) {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("Failed to read {}: {e}", args.input.display());
-            std::process::exit(1);
-        }
-    };
-
-    process(contents);
-}
-
-fn process(data: String) {
-    let mut pointer = 50 as isize;
-    let mut zeros = 0;
-
-    for line in data.lines() {
-        let (i, first) = line.char_indices().next().unwrap();
-        let mut distance: isize = line[i + first.len_utf8()..].parse::<isize>().unwrap();
-        let mut extra_zeros = distance / 100;
-        distance = distance % 100;
-        match first {
-            'L' => {
-                if 0 == pointer {
-                    extra_zeros -= 1;
-                }
-                pointer -= distance;
-                if pointer < 0 {
-                    extra_zeros += 1;
-                    pointer += 100;
-                }
-            }
-            'R' => {
-                pointer += distance;
-                if pointer > 99 {
-                    if pointer > 100 {
-                        extra_zeros += 1;
-                    }
-                    pointer -= 100;
-                }
-            }
-            _ => {
-                panic!("Unexpected value in {line}");
-            }
-        }
-        zeros += extra_zeros;
-        if 0 == pointer {
-            zeros += 1;
-        }
-    }
-    println!("{zeros}");
+    aoc25_01::run();
 }
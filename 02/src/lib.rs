@@ -0,0 +1,147 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, next_field, number};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day 02
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(2, &args.inputs, args.format, None, parse, process);
+}
+
+/// The comma separated `start-end` ranges, in order
+pub fn parse(data: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    let mut ranges = vec![];
+    for (i, line) in data.lines().enumerate() {
+        for range in line.split(',').filter(|range| !range.trim().is_empty()) {
+            let mut ids = range.split('-');
+            let start = number(i + 1, next_field(i + 1, &mut ids, "start")?)?;
+            let end = number(i + 1, next_field(i + 1, &mut ids, "end")?)?;
+            if let Some(extra) = ids.next() {
+                let why = format!("{range:?} has more than two ids (then {extra:?})");
+                return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+            }
+            ranges.push((start, end));
+        }
+    }
+    if ranges.is_empty() {
+        return Err(ParseError::new(0, ErrorKind::Empty));
+    }
+    Ok(ranges)
+}
+
+fn process(ranges: Vec<(usize, usize)>, report: &mut Report) {
+    let start = Instant::now();
+    let invalids = sum_invalid(&ranges);
+    report.answer(2, invalids, start.elapsed());
+}
+
+/// Adds up the ids in the ranges that are some run of digits repeated at
+/// least twice. Anything repeated a whole number of times is also repeated a
+/// prime number of times, and ids have at most 20 digits, so only prime
+/// repeat counts up to 19 need checking.
+fn sum_invalid(ranges: &[(usize, usize)]) -> usize {
+    let mut invalids = 0;
+
+    for &(start, end) in ranges {
+        'outer: for id in start..=end {
+            let strval: Vec<char> = id.to_string().chars().collect();
+            let len = strval.len();
+
+            'div: for divisor in [2, 3, 5, 7, 11, 13, 17, 19] {
+                if !len.is_multiple_of(divisor) {
+                    continue;
+                }
+                let segment_len = len / divisor;
+
+                for segment in 1..divisor {
+                    for i in 0..len / divisor {
+                        if strval[i] != strval[segment * segment_len + i] {
+                            continue 'div;
+                        }
+                    }
+                }
+
+                invalids += id;
+                continue 'outer;
+            }
+        }
+    }
+
+    invalids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Tries every way of cutting each id into equal runs
+    fn brute_force(ranges: &[(usize, usize)]) -> usize {
+        ranges
+            .iter()
+            .flat_map(|&(start, end)| start..=end)
+            .filter(|id| {
+                let digits = id.to_string();
+                let len = digits.len();
+                (1..len)
+                    .any(|run| len.is_multiple_of(run) && digits[..run].repeat(len / run) == digits)
+            })
+            .sum()
+    }
+
+    /// Short ranges, some anywhere and some around an id that's a run of
+    /// digits repeated, which can be long enough to need every repeat count
+    fn ranges() -> impl Strategy<Value = Vec<(usize, usize)>> {
+        let anywhere =
+            (0..100_000_usize, 0..200_usize).prop_map(|(start, len)| (start, start + len));
+        let repeated = (1..1000_usize, 2..20_usize, 0..50_usize, 0..50_usize).prop_map(
+            |(run, times, before, after)| {
+                let run = run.to_string();
+                let id: usize = run.repeat(times.min(19 / run.len())).parse().unwrap();
+                (id.saturating_sub(before), id + after)
+            },
+        );
+        prop::collection::vec(prop_oneof![anywhere, repeated], 0..10)
+    }
+
+    #[test]
+    fn test_example() {
+        let ranges = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        assert_eq!(4174379265, sum_invalid(&ranges));
+    }
+
+    #[test]
+    fn test_long_repeats() {
+        // eleven 1s is only a repeat of 1, eleven times
+        assert_eq!(11111111111, sum_invalid(&[(11111111110, 11111111112)]));
+        assert_eq!(0, sum_invalid(&[(11111111112, 11111111120)]));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(ranges in ranges()) {
+            prop_assert_eq!(brute_force(&ranges), sum_invalid(&ranges));
+        }
+    }
+}
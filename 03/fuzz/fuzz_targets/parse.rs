@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Parsing has to turn anything at all into either a puzzle or an error
+fuzz_target!(|data: &[u8]| {
+    let _ = aoc_03::parse(&String::from_utf8_lossy(data));
+});
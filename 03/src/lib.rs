@@ -0,0 +1,127 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day 03
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(3, &args.inputs, args.format, None, parse, process);
+}
+
+const JOLT_LEN: usize = 12;
+
+/// Each bank's battery joltages, which must be at least `JOLT_LEN` digits
+pub fn parse(data: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let mut banks = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let bank = line
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .ok_or(ParseError::new(i + 1, ErrorKind::UnexpectedChar(c)))
+            })
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+        if bank.len() < JOLT_LEN {
+            let why = format!(
+                "a bank needs at least {JOLT_LEN} batteries, not {}",
+                bank.len()
+            );
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        banks.push(bank);
+    }
+    Ok(banks)
+}
+
+fn process(banks: Vec<Vec<u32>>, report: &mut Report) {
+    let start = Instant::now();
+    let total: u64 = banks.iter().map(|bank| max_joltage(bank)).sum();
+    report.answer(2, total, start.elapsed());
+}
+
+/// The biggest number made of `JOLT_LEN` of the bank's digits, in order:
+/// each digit is the biggest that still leaves enough after it for the rest
+fn max_joltage(bank: &[u32]) -> u64 {
+    let mut vals = vec![];
+    let mut next_start = 0;
+    for i in 0..JOLT_LEN {
+        let stopping_point = bank.len() - (JOLT_LEN - 1 - i);
+        let mut max_val = 0;
+        let mut max_index = next_start;
+        for (i, &val) in bank
+            .iter()
+            .enumerate()
+            .take(stopping_point)
+            .skip(next_start)
+        {
+            if val > max_val {
+                max_val = val;
+                max_index = i;
+            }
+        }
+
+        vals.push(max_val);
+        next_start = max_index + 1;
+    }
+
+    vals.iter()
+        .map(|val| val.to_string())
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Tries every choice of `JOLT_LEN` batteries
+    fn brute_force(bank: &[u32]) -> u64 {
+        (0..1_u32 << bank.len())
+            .filter(|chosen| chosen.count_ones() as usize == JOLT_LEN)
+            .map(|chosen| {
+                bank.iter()
+                    .enumerate()
+                    .filter(|(i, _)| chosen & (1 << i) != 0)
+                    .fold(0, |joltage, (_, val)| joltage * 10 + u64::from(*val))
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_example() {
+        let banks = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        let total: u64 = banks.iter().map(|bank| max_joltage(bank)).sum();
+        assert_eq!(3121910778619, total);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(bank in prop::collection::vec(0..10_u32, JOLT_LEN..17)) {
+            prop_assert_eq!(brute_force(&bank), max_joltage(&bank));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day 04
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(4, &args.inputs, args.format, None, parse, process);
+}
+
+/// The grid of rolls (`@`) and empty spaces (`.`), every row the same width
+pub fn parse(data: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut grid: Vec<Vec<char>> = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let row: Vec<char> = line.chars().collect();
+        if let Some(c) = row.iter().find(|c| !matches!(c, '@' | '.')) {
+            return Err(ParseError::new(i + 1, ErrorKind::UnexpectedChar(*c)));
+        }
+        if let Some(first) = grid.first()
+            && first.len() != row.len()
+        {
+            let why = format!("{} wide, but the first row is {}", row.len(), first.len());
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        grid.push(row);
+    }
+    Ok(grid)
+}
+
+fn process(grid: Vec<Vec<char>>, report: &mut Report) {
+    let start = Instant::now();
+    let total_removed = remove_rolls(grid);
+    report.answer(2, total_removed, start.elapsed());
+}
+
+/// Keeps taking away every roll with fewer than 4 rolls next to it, until
+/// there are none left to take; returns how many went
+fn remove_rolls(mut grid: Vec<Vec<char>>) -> usize {
+    let mut total_removed = 0;
+    loop {
+        let mut removeable = 0;
+        let mut next_grid = grid.clone();
+        for r in 0..grid.len() {
+            for c in 0..grid[0].len() {
+                if grid[r][c] == '@' && adjacent_rolls(&grid, r as isize, c as isize) < 4 {
+                    removeable += 1;
+                    next_grid[r][c] = 'x';
+                    // print!("x");
+                } else {
+                    // print!("{}", grid[r][c]);
+                }
+            }
+            // println!("");
+        }
+        // println!("{removeable}");
+        if removeable == 0 {
+            break;
+        }
+        total_removed += removeable;
+        grid = next_grid;
+    }
+    total_removed
+}
+
+fn adjacent_rolls(grid: &[Vec<char>], r: isize, c: isize) -> usize {
+    // println!("Evaluating {r},{c}: {}", grid[r as usize][c as usize]);
+    let mut adjacent: isize = -1; // -1 because we're going to end up counting ourselves
+    for ro in -1isize..=1 {
+        for co in -1isize..=1 {
+            // println!("  checking {},{}", r + ro, c + co);
+            if is_roll(grid, r + ro, c + co) {
+                // println!("    {},{} is a roll", r + ro, c + co);
+                adjacent += 1;
+            }
+        }
+    }
+    adjacent as usize
+}
+
+fn is_roll(grid: &[Vec<char>], r: isize, c: isize) -> bool {
+    r >= 0
+        && r < grid.len() as isize
+        && c >= 0
+        && c < grid[0].len() as isize
+        && grid[r as usize][c as usize] == '@'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Takes away one roll at a time, whichever it finds first. Taking a roll
+    /// away never makes another one harder to take, so the order doesn't
+    /// change how many go in the end.
+    fn brute_force(mut grid: Vec<Vec<char>>) -> usize {
+        let neighbours = |grid: &[Vec<char>], r: usize, c: usize| {
+            let mut rolls = 0;
+            for nr in r.saturating_sub(1)..=r + 1 {
+                for nc in c.saturating_sub(1)..=c + 1 {
+                    let cell = grid.get(nr).and_then(|row| row.get(nc));
+                    if (nr, nc) != (r, c) && cell == Some(&'@') {
+                        rolls += 1;
+                    }
+                }
+            }
+            rolls
+        };
+
+        let mut removed = 0;
+        'again: loop {
+            for r in 0..grid.len() {
+                for c in 0..grid[r].len() {
+                    if grid[r][c] == '@' && neighbours(&grid, r, c) < 4 {
+                        grid[r][c] = '.';
+                        removed += 1;
+                        continue 'again;
+                    }
+                }
+            }
+            return removed;
+        }
+    }
+
+    /// Mostly rolls, so there's a good bit of taking away to do
+    fn grids() -> impl Strategy<Value = Vec<Vec<char>>> {
+        (1..12_usize, 1..12_usize).prop_flat_map(|(rows, cols)| {
+            let cell = prop::sample::select(vec!['@', '@', '@', '.']);
+            prop::collection::vec(prop::collection::vec(cell, cols), rows)
+        })
+    }
+
+    #[test]
+    fn test_example() {
+        let grid = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        assert_eq!(43, remove_rolls(grid));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(grid in grids()) {
+            prop_assert_eq!(brute_force(grid.clone()), remove_rolls(grid));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, number};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day 05
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(5, &args.inputs, args.format, None, parse, process);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Range {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Range {
+    fn includes(&self, val: u64) -> bool {
+        self.min <= val && val <= self.max
+    }
+}
+
+fn process(ranges: Vec<Range>, report: &mut Report) {
+    let start = Instant::now();
+    let fresh = count_fresh(ranges);
+    report.answer(2, fresh, start.elapsed());
+}
+
+/// The ranges before the first blank line. The IDs after it aren't needed,
+/// but still have to be numbers.
+pub fn parse(data: &str) -> Result<Vec<Range>, ParseError> {
+    let mut ranges: Vec<Range> = vec![];
+    let mut lines = data.lines().enumerate();
+
+    for (i, line) in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let Some((min, max)) = line.split_once("-") else {
+            return Err(ParseError::new(i + 1, ErrorKind::Missing("'-'")));
+        };
+        let range = Range {
+            min: number(i + 1, min)?,
+            max: number(i + 1, max)?,
+        };
+        if range.min > range.max {
+            let why = format!("{line} runs backwards");
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        ranges.push(range);
+    }
+    for (i, line) in lines {
+        number::<u64>(i + 1, line)?;
+    }
+
+    Ok(ranges)
+}
+
+/// How many IDs are in at least one of the ranges
+fn count_fresh(mut ranges: Vec<Range>) -> u64 {
+    loop {
+        let (new_ranges, merge_count) = merge_ranges(&ranges);
+        ranges = new_ranges;
+        if merge_count == 0 {
+            break;
+        }
+    }
+
+    ranges.iter().map(|range| range.max - range.min + 1).sum()
+}
+
+fn merge_ranges(ranges: &[Range]) -> (Vec<Range>, usize) {
+    let mut merged: Vec<Range> = vec![];
+    let mut merged_count = 0;
+    'range_loop: for range in ranges.iter() {
+        let merged_len = merged.len();
+        for m in 0..merged_len {
+            let merge = &merged[m];
+            if merge.includes(range.min) {
+                if merge.includes(range.max) {
+                    // do nothing; this range is already covered
+                    merged_count += 1;
+                    continue 'range_loop;
+                } else {
+                    // merge range and merge
+                    merged.push(Range {
+                        min: merge.min,
+                        max: range.max,
+                    });
+                    merged.swap_remove(m);
+                    merged_count += 1;
+                    continue 'range_loop;
+                }
+            } else if merge.includes(range.max) {
+                // merge range and merge
+                merged.push(Range {
+                    min: range.min,
+                    max: merge.max,
+                });
+                merged.swap_remove(m);
+                merged_count += 1;
+                continue 'range_loop;
+            } else if range.includes(merge.min) && range.includes(merge.max) {
+                // range covers merge, so remove merge and add range
+                merged.push((*range).clone());
+                merged.swap_remove(m);
+                merged_count += 1;
+            }
+        }
+        // if we got here, this range is distinct
+        merged.push((*range).clone());
+    }
+    (merged, merged_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    /// Lists every ID
+    fn brute_force(ranges: &[Range]) -> u64 {
+        let ids: HashSet<u64> = ranges.iter().flat_map(|r| r.min..=r.max).collect();
+        ids.len() as u64
+    }
+
+    /// Small, heavily overlapping ranges
+    fn ranges() -> impl Strategy<Value = Vec<Range>> {
+        prop::collection::vec(
+            (0..200_u64, 0..40_u64).prop_map(|(min, len)| Range {
+                min,
+                max: min + len,
+            }),
+            0..30,
+        )
+    }
+
+    #[test]
+    fn test_example() {
+        let ranges = parse("3-5\n10-14\n16-20\n12-18\n\n1\n5").unwrap();
+        assert_eq!(14, count_fresh(ranges));
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        assert_eq!(
+            ParseError::new(2, ErrorKind::Missing("'-'")),
+            parse("1-2\n3\n").unwrap_err()
+        );
+        assert!(matches!(
+            parse("5-3").unwrap_err().kind,
+            ErrorKind::Invalid(_)
+        ));
+        assert_eq!(
+            ParseError::new(4, ErrorKind::BadNumber("x".to_string())),
+            parse("1-2\n\n1\nx").unwrap_err()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(ranges in ranges()) {
+            prop_assert_eq!(brute_force(&ranges), count_fresh(ranges));
+        }
+    }
+}
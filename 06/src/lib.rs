@@ -0,0 +1,187 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, number};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day 06
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(6, &args.inputs, args.format, None, parse, process);
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    pub nums: Vec<usize>,
+    pub op: char,
+}
+
+impl Problem {
+    fn result(&self) -> usize {
+        match self.op {
+            '+' => self.nums.iter().sum(),
+            '*' => self.nums.iter().product(),
+            _ => unreachable!("parse only allows + and *"),
+        }
+    }
+}
+
+fn process(problems: Vec<Problem>, report: &mut Report) {
+    let start = Instant::now();
+    let total: usize = problems.iter().map(|problem| problem.result()).sum();
+    report.answer(2, total, start.elapsed());
+}
+
+/// Reads the worksheet a column at a time, right to left, with each number
+/// written top to bottom and each problem ending at its operator
+pub fn parse(data: &str) -> Result<Vec<Problem>, ParseError> {
+    let mut char_grid: Vec<Vec<char>> = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let row: Vec<char> = line.chars().collect();
+        if let Some(c) = row
+            .iter()
+            .find(|c| !matches!(c, ' ' | '*' | '+') && !c.is_ascii_digit())
+        {
+            return Err(ParseError::new(i + 1, ErrorKind::UnexpectedChar(*c)));
+        }
+        char_grid.push(row);
+    }
+    if char_grid.is_empty() {
+        return Err(ParseError::new(0, ErrorKind::Empty));
+    }
+
+    // editors like to trim trailing spaces, so short rows are padded back out
+    let cols = char_grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    for row in char_grid.iter_mut() {
+        row.resize(cols, ' ');
+    }
+
+    let mut problems: Vec<Problem> = vec![];
+
+    let mut nums: Vec<usize> = vec![];
+    for c in (0..cols).rev() {
+        let mut val = "".to_string();
+        for (r, row) in char_grid.iter().enumerate() {
+            let each_char = row[c];
+            match each_char {
+                ' ' => continue,
+                '*' | '+' => {
+                    if val.is_empty() {
+                        return Err(ParseError::new(r + 1, ErrorKind::Missing("number")));
+                    }
+                    nums.push(number(r + 1, &val)?);
+                    problems.push(Problem {
+                        nums,
+                        op: each_char,
+                    });
+
+                    nums = vec![];
+                    val = "".to_string();
+                }
+                _ => val.push(each_char),
+            };
+        }
+        if !val.is_empty() {
+            nums.push(number(char_grid.len(), &val)?);
+        }
+    }
+    if !nums.is_empty() {
+        let why = format!("{nums:?} don't have an operator");
+        return Err(ParseError::new(0, ErrorKind::Invalid(why)));
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Works the problems out directly from their numbers
+    fn brute_force(problems: &[(Vec<usize>, char)]) -> usize {
+        problems
+            .iter()
+            .map(|(nums, op)| match op {
+                '+' => nums.iter().sum::<usize>(),
+                _ => nums.iter().product(),
+            })
+            .sum()
+    }
+
+    /// Writes the problems out the way `parse` reads them: the first problem
+    /// furthest right, each number down a column with the first number on the
+    /// right, and the operator under the last one
+    fn worksheet(problems: &[(Vec<usize>, char)]) -> String {
+        let mut columns: Vec<String> = vec![];
+        for (p, (nums, op)) in problems.iter().enumerate().rev() {
+            for (n, num) in nums.iter().enumerate().rev() {
+                let mut column = num.to_string();
+                if n == nums.len() - 1 {
+                    column.push(*op);
+                }
+                columns.push(column);
+            }
+            if p > 0 {
+                columns.push(String::new());
+            }
+        }
+
+        let height = columns.iter().map(|column| column.len()).max().unwrap_or(0);
+        let mut rows = vec![String::new(); height];
+        for column in columns {
+            // the operator has to be on the bottom row, under every digit
+            let padded = format!("{column:>height$}");
+            for (row, cell) in rows.iter_mut().zip(padded.chars()) {
+                row.push(cell);
+            }
+        }
+        rows.join("\n")
+    }
+
+    fn problems() -> impl Strategy<Value = Vec<(Vec<usize>, char)>> {
+        let problem = (
+            prop::collection::vec(0..10_000_usize, 1..5),
+            prop_oneof![Just('+'), Just('*')],
+        );
+        prop::collection::vec(problem, 1..8)
+    }
+
+    #[test]
+    fn test_example() {
+        let problems = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        let total: usize = problems.iter().map(|problem| problem.result()).sum();
+        assert_eq!(3263827, total);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(problems in problems()) {
+            let parsed = parse(&worksheet(&problems)).unwrap();
+            let read: Vec<(Vec<usize>, char)> =
+                parsed.iter().map(|problem| (problem.nums.clone(), problem.op)).collect();
+            prop_assert_eq!(&problems, &read);
+            let total: usize = parsed.iter().map(|problem| problem.result()).sum();
+            prop_assert_eq!(brute_force(&problems), total);
+        }
+    }
+}
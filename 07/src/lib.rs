@@ -0,0 +1,204 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day 07
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(7, &args.inputs, args.format, None, parse, process);
+}
+
+#[derive(Debug)]
+pub struct Manifold {
+    pub cols: usize,
+    /// Where the beam comes in, on the first row
+    pub start: usize,
+    /// The columns with splitters, for each row after the first
+    pub splitters: Vec<Vec<usize>>,
+}
+
+fn process(manifold: Manifold, report: &mut Report) {
+    let start = Instant::now();
+    let total = count_timelines(&manifold);
+    report.answer(2, total, start.elapsed());
+}
+
+/// Carries the number of timelines in each column down a row at a time; a
+/// splitter sends all of its column's timelines both left and right
+fn count_timelines(manifold: &Manifold) -> usize {
+    let mut timelines = vec![0; manifold.cols];
+
+    timelines[manifold.start] = 1;
+
+    for splitter_indices in &manifold.splitters {
+        for &splitter in splitter_indices {
+            if timelines[splitter] > 0 {
+                timelines[splitter - 1] += timelines[splitter];
+                timelines[splitter + 1] += timelines[splitter];
+                timelines[splitter] = 0;
+            }
+        }
+    }
+
+    timelines.iter().sum()
+}
+
+/// The first row has the start (`S`), and every row after it can have
+/// splitters (`^`), though never at the edges where there'd be nowhere to
+/// split to
+pub fn parse(data: &str) -> Result<Manifold, ParseError> {
+    let mut lines = data.lines().enumerate();
+
+    let Some((_, first)) = lines.next() else {
+        return Err(ParseError::new(0, ErrorKind::Empty));
+    };
+    let chars: Vec<char> = first.chars().collect();
+    let cols = chars.len();
+    if let Some(c) = chars.iter().find(|&&c| c != '.' && c != 'S') {
+        return Err(ParseError::new(1, ErrorKind::UnexpectedChar(*c)));
+    }
+    let Some(start) = chars.iter().position(|&c| c == 'S') else {
+        return Err(ParseError::new(1, ErrorKind::Missing("'S'")));
+    };
+    if chars.iter().filter(|&&c| c == 'S').count() > 1 {
+        let why = "more than one 'S'".to_string();
+        return Err(ParseError::new(1, ErrorKind::Invalid(why)));
+    }
+
+    let mut splitters = vec![];
+    for (i, line) in lines {
+        let chars: Vec<char> = line.chars().collect();
+        if chars.len() != cols {
+            let why = format!("{} wide, but the first row is {cols}", chars.len());
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        if let Some(c) = chars.iter().find(|&&c| c != '.' && c != '^') {
+            return Err(ParseError::new(i + 1, ErrorKind::UnexpectedChar(*c)));
+        }
+
+        let splitter_indices: Vec<usize> = chars
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &c)| (c == '^').then_some(i))
+            .collect();
+        if splitter_indices.contains(&0) || splitter_indices.contains(&(cols - 1)) {
+            let why = "a splitter at the edge".to_string();
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        splitters.push(splitter_indices);
+    }
+
+    Ok(Manifold {
+        cols,
+        start,
+        splitters,
+    })
+}
+
+/*
+
+.......S....... 1 timeline
+.......1.......
+......1^1...... adds 1 = 2
+......1.1......
+.....1^2^1..... left adds 1; right adds 1; = 4
+.....1.2.1.....
+....1^3^3^1.... left adds 1; middle gets hit twice so adds 2; right adds 1; = 8
+....1.3.3.1....
+...1^4^331^1... left adds 1; middle gets hit thrice so adds 3; right adds 1; 13
+...1.4.331.1...
+..1^5^434^2^1.. 20
+..1.5.434.2.1..
+.1^154^74.21^1. 26
+.1.154.74.21.1.
+1^2^0^1^1^211^1 40
+...............
+
+*/
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Follows every timeline down on its own
+    fn brute_force(manifold: &Manifold) -> usize {
+        let mut timelines = 0;
+        let mut todo = vec![(0, manifold.start)];
+        while let Some((row, col)) = todo.pop() {
+            match manifold.splitters.get(row) {
+                None => timelines += 1,
+                Some(splitters) if splitters.contains(&col) => {
+                    todo.push((row + 1, col - 1));
+                    todo.push((row + 1, col + 1));
+                }
+                Some(_) => todo.push((row + 1, col)),
+            }
+        }
+        timelines
+    }
+
+    /// Up to 12 rows, so at most 4096 timelines to follow. Like real
+    /// manifolds, splitters are never right next to each other.
+    fn manifolds() -> impl Strategy<Value = String> {
+        (3..12_usize, 0..12_usize)
+            .prop_flat_map(|(cols, rows)| {
+                let start = 0..cols;
+                let splitters =
+                    prop::collection::vec(prop::collection::vec(any::<bool>(), cols), rows);
+                (Just(cols), start, splitters)
+            })
+            .prop_map(|(cols, start, splitters)| {
+                let mut lines = vec![
+                    (0..cols)
+                        .map(|c| if c == start { 'S' } else { '.' })
+                        .collect::<String>(),
+                ];
+                for row in splitters {
+                    let mut line = vec!['.'; cols];
+                    for c in 1..cols - 1 {
+                        if row[c] && line[c - 1] != '^' {
+                            line[c] = '^';
+                        }
+                    }
+                    lines.push(line.into_iter().collect());
+                }
+                lines.join("\n")
+            })
+    }
+
+    #[test]
+    fn test_example() {
+        let manifold = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        assert_eq!(40, count_timelines(&manifold));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(text in manifolds()) {
+            let manifold = parse(&text).unwrap();
+            prop_assert_eq!(brute_force(&manifold), count_timelines(&manifold));
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, next_field, number};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{fs, path::PathBuf, time::Instant};
+
+/// AOC 25 day 08
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Write the chosen connections to this path as an edge list
+    #[arg(long)]
+    edges: Option<PathBuf>,
+
+    /// Write the chosen connections to this path as a Graphviz DOT graph
+    #[arg(long)]
+    dot: Option<PathBuf>,
+
+    /// Write the chosen connections to this path as a Wavefront OBJ line set
+    #[arg(long)]
+    obj: Option<PathBuf>,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    let exports = [
+        ("--edges", &args.edges),
+        ("--dot", &args.dot),
+        ("--obj", &args.obj),
+    ];
+    let single = exports
+        .into_iter()
+        .find(|(_, path)| path.is_some())
+        .map(|(option, _)| option);
+
+    day::run(
+        8,
+        &args.inputs,
+        args.format,
+        single,
+        parse,
+        |boxes, report| {
+            let connections = process(&boxes, report);
+
+            if let Some(path) = &args.edges {
+                write_export(path, to_edge_list(&connections), report);
+            }
+            if let Some(path) = &args.dot {
+                write_export(path, to_dot(&boxes, &connections), report);
+            }
+            if let Some(path) = &args.obj {
+                write_export(path, to_obj(&boxes, &connections), report);
+            }
+        },
+    );
+}
+
+fn write_export(path: &PathBuf, contents: String, report: &mut Report) {
+    if let Err(e) = fs::write(path, contents) {
+        report.fail(format!("Failed to write {}: {e}", path.display()));
+    }
+}
+
+/// One line per connection: `left right distance`, using box indexes from the input
+fn to_edge_list(connections: &[Distance]) -> String {
+    connections
+        .iter()
+        .map(|c| format!("{} {} {}\n", c.left_idx, c.right_idx, c.distance))
+        .collect()
+}
+
+fn to_dot(boxes: &[JunctionBox], connections: &[Distance]) -> String {
+    let mut out = String::from("graph circuit {\n");
+    for (i, jbox) in boxes.iter().enumerate() {
+        out.push_str(&format!(
+            "  n{i} [label=\"{},{},{}\"];\n",
+            jbox.x, jbox.y, jbox.z
+        ));
+    }
+    for c in connections {
+        out.push_str(&format!(
+            "  n{} -- n{} [label=\"{:.2}\"];\n",
+            c.left_idx, c.right_idx, c.distance
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Vertices are the boxes in input order; OBJ indexes are 1-based
+fn to_obj(boxes: &[JunctionBox], connections: &[Distance]) -> String {
+    let mut out = String::new();
+    for jbox in boxes {
+        out.push_str(&format!("v {} {} {}\n", jbox.x, jbox.y, jbox.z));
+    }
+    for c in connections {
+        out.push_str(&format!("l {} {}\n", c.left_idx + 1, c.right_idx + 1));
+    }
+    out
+}
+
+#[derive(Debug, PartialEq)]
+pub struct JunctionBox {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+}
+
+impl JunctionBox {
+    fn distance_to(&self, other: &JunctionBox) -> f64 {
+        let dx = self.x as f64 - other.x as f64;
+        let dy = self.y as f64 - other.y as f64;
+        let dz = self.z as f64 - other.z as f64;
+
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+}
+
+#[derive(Debug)]
+struct Distance {
+    left_idx: usize,
+    right_idx: usize,
+    distance: f64,
+}
+
+#[derive(Debug)]
+struct Circuit {
+    boxes: Vec<usize>,
+}
+
+impl Circuit {
+    fn append(&mut self, circuit: &mut Circuit) {
+        self.boxes.append(&mut circuit.boxes)
+    }
+
+    fn contains(&self, jbox_idx: &usize) -> bool {
+        self.boxes.contains(jbox_idx)
+    }
+
+    fn len(&self) -> usize {
+        self.boxes.len()
+    }
+
+    fn push(&mut self, jbox_idx: usize) {
+        self.boxes.push(jbox_idx);
+    }
+}
+
+/// One `x,y,z` box per line, and at least one of them
+pub fn parse(data: &str) -> Result<Vec<JunctionBox>, ParseError> {
+    let mut boxes = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let mut split = line.split(",");
+        boxes.push(JunctionBox {
+            x: number(i + 1, next_field(i + 1, &mut split, "x")?)?,
+            y: number(i + 1, next_field(i + 1, &mut split, "y")?)?,
+            z: number(i + 1, next_field(i + 1, &mut split, "z")?)?,
+        });
+        if split.next().is_some() {
+            let why = format!("{line:?} has more than three coordinates");
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+    }
+    if boxes.is_empty() {
+        return Err(ParseError::new(0, ErrorKind::Empty));
+    }
+    Ok(boxes)
+}
+
+/// Returns the connections that were actually used to join circuits (i.e. the
+/// minimum spanning tree), in the order they were made
+fn process(boxes: &[JunctionBox], report: &mut Report) -> Vec<Distance> {
+    let start = Instant::now();
+    let mut distances: Vec<Distance> = vec![];
+    for i in 0..boxes.len() - 1 {
+        for j in i + 1..boxes.len() {
+            distances.push(Distance {
+                left_idx: i,
+                right_idx: j,
+                distance: boxes[i].distance_to(&boxes[j]),
+            });
+        }
+    }
+    distances.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+    let mut circuits: Vec<Circuit> = vec![];
+    let mut connections: Vec<Distance> = vec![];
+    for distance in distances {
+        let left_circuit_idx = circuits.iter().position(|c| c.contains(&distance.left_idx));
+        let right_circuit_idx = circuits
+            .iter()
+            .position(|c| c.contains(&distance.right_idx));
+        let joined = match (left_circuit_idx, right_circuit_idx) {
+            (Some(lci), Some(rci)) => {
+                if lci != rci {
+                    let (earlier, later) = if lci < rci { (lci, rci) } else { (rci, lci) };
+                    let (first_part, last_part) = circuits.split_at_mut(later);
+                    let left_circuit = &mut first_part[earlier];
+                    let right_circuit = &mut last_part[0];
+                    left_circuit.append(right_circuit);
+
+                    circuits.remove(later);
+                    true
+                } else {
+                    false
+                }
+            }
+            (Some(lci), None) => {
+                circuits[lci].push(distance.right_idx);
+                true
+            }
+            (None, Some(rci)) => {
+                circuits[rci].push(distance.left_idx);
+                true
+            }
+            (None, None) => {
+                let new_circuit = Circuit {
+                    boxes: vec![distance.left_idx, distance.right_idx],
+                };
+                circuits.push(new_circuit);
+                true
+            }
+        };
+        let done = circuits.len() == 1 && circuits[0].len() == boxes.len();
+        if done {
+            let product = boxes[distance.left_idx].x * boxes[distance.right_idx].x;
+            report.answer(2, product, start.elapsed());
+        }
+        if joined {
+            connections.push(distance);
+        }
+        if done {
+            break;
+        }
+    }
+
+    connections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::input::Input;
+    use aoc::report::Run;
+    use proptest::prelude::*;
+
+    /// Adds the closest pairs one at a time, flood filling from the first box
+    /// after each, until that reaches every box; returns the distance of the
+    /// pair that did it
+    fn brute_force(boxes: &[JunctionBox]) -> f64 {
+        let mut pairs: Vec<(usize, usize)> = (0..boxes.len())
+            .flat_map(|i| (i + 1..boxes.len()).map(move |j| (i, j)))
+            .collect();
+        let distance = |(i, j): (usize, usize)| boxes[i].distance_to(&boxes[j]);
+        pairs.sort_by(|a, b| distance(*a).total_cmp(&distance(*b)));
+
+        for made in 1..=pairs.len() {
+            let mut reached = vec![false; boxes.len()];
+            reached[0] = true;
+            let mut todo = vec![0];
+            while let Some(at) = todo.pop() {
+                for &(i, j) in &pairs[..made] {
+                    for (from, to) in [(i, j), (j, i)] {
+                        if from == at && !reached[to] {
+                            reached[to] = true;
+                            todo.push(to);
+                        }
+                    }
+                }
+            }
+            if reached.iter().all(|r| *r) {
+                return distance(pairs[made - 1]);
+            }
+        }
+        unreachable!("every pair connects everything")
+    }
+
+    /// Small coordinates, so there are plenty of ties and repeats
+    fn boxes() -> impl Strategy<Value = Vec<JunctionBox>> {
+        let jbox =
+            (0..20_usize, 0..20_usize, 0..20_usize).prop_map(|(x, y, z)| JunctionBox { x, y, z });
+        prop::collection::vec(jbox, 2..12)
+    }
+
+    #[test]
+    fn test_exports() {
+        let boxes = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        let mut run = Run::new(8, Format::Json);
+        let report = run.start(&Input::File(PathBuf::from("test.txt")), false);
+        let connections = process(&boxes, report);
+        assert_eq!("25272", report.answers[0].answer);
+
+        // a spanning tree: n - 1 connections, each joining two circuits
+        assert_eq!(boxes.len() - 1, connections.len());
+        let mut circuit: Vec<usize> = (0..boxes.len()).collect();
+        for c in &connections {
+            let (left, right) = (circuit[c.left_idx], circuit[c.right_idx]);
+            assert_ne!(left, right, "{c:?} joins a circuit to itself");
+            circuit
+                .iter_mut()
+                .filter(|id| **id == right)
+                .for_each(|id| *id = left);
+        }
+
+        assert_eq!(
+            connections.len(),
+            to_edge_list(&connections).lines().count()
+        );
+        let dot = to_dot(&boxes, &connections);
+        assert_eq!(connections.len(), dot.matches(" -- ").count());
+
+        let obj = to_obj(&boxes, &connections);
+        let vertices = obj.lines().filter(|line| line.starts_with("v ")).count();
+        assert_eq!(boxes.len(), vertices);
+        let lines: Vec<&str> = obj.lines().filter(|line| line.starts_with("l ")).collect();
+        assert_eq!(connections.len(), lines.len());
+        for line in lines {
+            for index in line.split_whitespace().skip(1) {
+                let index: usize = index.parse().unwrap();
+                assert!((1..=vertices).contains(&index), "{line:?} is out of range");
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(boxes in boxes()) {
+            let mut run = Run::new(8, Format::Json);
+            let report = run.start(&Input::Stdin, false);
+            let connections = process(&boxes, report);
+            prop_assert_eq!(boxes.len() - 1, connections.len());
+
+            // ties can make a different pair the last one, but not a
+            // different distance
+            let last = connections.last().unwrap();
+            prop_assert_eq!(brute_force(&boxes), last.distance);
+            let product = boxes[last.left_idx].x * boxes[last.right_idx].x;
+            prop_assert_eq!(product.to_string(), report.answers[0].answer.clone());
+        }
+    }
+}
@@ -0,0 +1,570 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, number};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use serde::Serialize;
+use std::{collections::HashMap, fmt, fs, path::PathBuf, time::Instant};
+
+/// AOC 25 day 09
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Write an SVG of the red tile loop and the best rectangles to this path
+    #[arg(long)]
+    svg: Option<PathBuf>,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    let single = args.svg.as_ref().map(|_| "--svg");
+
+    day::run(
+        9,
+        &args.inputs,
+        args.format,
+        single,
+        parse,
+        |reds, report| {
+            match validate(&reds) {
+                Ok(winding) => report.say(format!("red tiles wind {winding}")),
+                Err(problems) => {
+                    let problems: Vec<String> = problems
+                        .iter()
+                        .map(|p| format!("{}: {p}", report.input))
+                        .collect();
+                    report.fail(problems.join("\n"));
+                    return;
+                }
+            }
+
+            let bests = process(&reds, report);
+
+            if let Some(path) = &args.svg
+                && let Err(e) = fs::write(path, to_svg(&reds, &bests))
+            {
+                report.fail(format!("Failed to write {}: {e}", path.display()));
+            }
+        },
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Tile {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// As it's written in the input
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Winding {
+    Clockwise,
+    Counterclockwise,
+}
+
+impl fmt::Display for Winding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Winding::Clockwise => write!(f, "clockwise"),
+            Winding::Counterclockwise => write!(f, "counterclockwise"),
+        }
+    }
+}
+
+/// Checks that the red tiles, in order, are the corners of a simple rectilinear
+/// polygon, and returns which way they wind (with y increasing downwards).
+/// Problems are reported against 1-based input line numbers.
+fn validate(reds: &[Tile]) -> Result<Winding, Vec<String>> {
+    let mut problems = vec![];
+    let n = reds.len();
+    if n < 4 {
+        problems.push(format!("need at least 4 red tiles, found {n}"));
+        return Err(problems);
+    }
+
+    let mut seen: HashMap<(usize, usize), usize> = HashMap::new();
+    for (i, tile) in reds.iter().enumerate() {
+        if let Some(earlier) = seen.insert((tile.x, tile.y), i) {
+            problems.push(format!(
+                "line {}: {tile} duplicates line {}",
+                i + 1,
+                earlier + 1
+            ));
+        }
+    }
+
+    for i in 0..n {
+        let (prev, tile, next) = (&reds[(i + n - 1) % n], &reds[i], &reds[(i + 1) % n]);
+        if tile.x != next.x && tile.y != next.y {
+            problems.push(format!(
+                "line {}: {tile} isn't in the same row or column as line {}: {next}",
+                i + 1,
+                (i + 1) % n + 1
+            ));
+        }
+        if (prev.x == tile.x && tile.x == next.x) || (prev.y == tile.y && tile.y == next.y) {
+            problems.push(format!(
+                "line {}: {tile} is in the middle of a straight run",
+                i + 1
+            ));
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    // edges that share a corner are allowed to touch there, all others mustn't touch at all
+    for i in 0..n {
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            let edge1 = (&reds[i], &reds[(i + 1) % n]);
+            let edge2 = (&reds[j], &reds[(j + 1) % n]);
+            if edges_touch(edge1, edge2) {
+                problems.push(format!(
+                    "line {}: edge {} -> {} crosses or touches edge {} -> {} from line {}",
+                    i + 1,
+                    edge1.0,
+                    edge1.1,
+                    edge2.0,
+                    edge2.1,
+                    j + 1
+                ));
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    // shoelace formula; positive is clockwise when y increases downwards
+    let twice_area: i64 = (0..n)
+        .map(|i| {
+            let (a, b) = (&reds[i], &reds[(i + 1) % n]);
+            a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64
+        })
+        .sum();
+    if twice_area > 0 {
+        Ok(Winding::Clockwise)
+    } else {
+        Ok(Winding::Counterclockwise)
+    }
+}
+
+/// Whether two axis-aligned edges share any point
+fn edges_touch(edge1: (&Tile, &Tile), edge2: (&Tile, &Tile)) -> bool {
+    let (left1, right1) = (edge1.0.x.min(edge1.1.x), edge1.0.x.max(edge1.1.x));
+    let (top1, bottom1) = (edge1.0.y.min(edge1.1.y), edge1.0.y.max(edge1.1.y));
+    let (left2, right2) = (edge2.0.x.min(edge2.1.x), edge2.0.x.max(edge2.1.x));
+    let (top2, bottom2) = (edge2.0.y.min(edge2.1.y), edge2.0.y.max(edge2.1.y));
+
+    left1 <= right2 && left2 <= right1 && top1 <= bottom2 && top2 <= bottom1
+}
+
+/// The floor, coordinate-compressed so that each distinct red tile x (or y) gets
+/// its own column (or row), with a column (or row) between each pair of them
+/// standing in for the whole gap, plus a padding column (or row) on each side.
+/// Each compressed cell is either inside the red/green loop or outside it.
+struct Floor {
+    xs: Vec<usize>,
+    ys: Vec<usize>,
+    /// 2D prefix sums of outside cells; `outside_sums[r][c]` counts the outside
+    /// cells in rows `0..r` and columns `0..c`
+    outside_sums: Vec<Vec<usize>>,
+}
+
+impl Floor {
+    /// The red tiles have to have passed `validate`
+    fn new(reds: &[Tile]) -> Floor {
+        let mut xs: Vec<usize> = reds.iter().map(|t| t.x).collect();
+        xs.sort();
+        xs.dedup();
+        let mut ys: Vec<usize> = reds.iter().map(|t| t.y).collect();
+        ys.sort();
+        ys.dedup();
+
+        let width = xs.len() * 2 + 1;
+        let height = ys.len() * 2 + 1;
+        let mut floor = Floor {
+            xs,
+            ys,
+            outside_sums: vec![],
+        };
+
+        let mut edge = vec![vec![false; width]; height];
+        for i in 0..reds.len() {
+            let (first, second) = (&reds[i], &reds[(i + 1) % reds.len()]);
+            let (c1, r1) = floor.compress(first);
+            let (c2, r2) = floor.compress(second);
+            for row in edge.iter_mut().take(r1.max(r2) + 1).skip(r1.min(r2)) {
+                for cell in row.iter_mut().take(c1.max(c2) + 1).skip(c1.min(c2)) {
+                    *cell = true;
+                }
+            }
+        }
+
+        // flood fill from the padding, which is always outside
+        let mut outside = vec![vec![false; width]; height];
+        let mut todo: Vec<(usize, usize)> = vec![(0, 0)];
+        outside[0][0] = true;
+        while let Some((r, c)) = todo.pop() {
+            let neighbors = [
+                (r.wrapping_sub(1), c),
+                (r + 1, c),
+                (r, c.wrapping_sub(1)),
+                (r, c + 1),
+            ];
+            for (nr, nc) in neighbors {
+                if nr < height && nc < width && !edge[nr][nc] && !outside[nr][nc] {
+                    outside[nr][nc] = true;
+                    todo.push((nr, nc));
+                }
+            }
+        }
+
+        // a gap between two neighbouring coordinates has no tiles in it, so it
+        // doesn't matter whether it's outside
+        let has_tiles = |coords: &[usize], i: usize| {
+            i % 2 == 1 || i == 0 || i == coords.len() * 2 || coords[i / 2] - coords[i / 2 - 1] > 1
+        };
+        let mut sums = vec![vec![0; width + 1]; height + 1];
+        for r in 0..height {
+            for c in 0..width {
+                let counts = outside[r][c] && has_tiles(&floor.ys, r) && has_tiles(&floor.xs, c);
+                sums[r + 1][c + 1] = sums[r][c + 1] + sums[r + 1][c] - sums[r][c] + counts as usize;
+            }
+        }
+        floor.outside_sums = sums;
+
+        floor
+    }
+
+    /// Returns the compressed (column, row) of a red tile
+    fn compress(&self, tile: &Tile) -> (usize, usize) {
+        let c = self.xs.binary_search(&tile.x).unwrap() * 2 + 1;
+        let r = self.ys.binary_search(&tile.y).unwrap() * 2 + 1;
+        (c, r)
+    }
+
+    /// Whether the rectangle with these two red tiles as opposite corners is
+    /// entirely inside the loop
+    fn contains(&self, first: &Tile, second: &Tile) -> bool {
+        let (c1, r1) = self.compress(first);
+        let (c2, r2) = self.compress(second);
+        let (left, right) = (c1.min(c2), c1.max(c2) + 1);
+        let (top, bottom) = (r1.min(r2), r1.max(r2) + 1);
+
+        let sums = &self.outside_sums;
+        sums[bottom][right] + sums[top][left] - sums[top][right] - sums[bottom][left] == 0
+    }
+}
+
+fn area(first: &Tile, second: &Tile) -> usize {
+    (second.x.abs_diff(first.x) + 1) * (second.y.abs_diff(first.y) + 1)
+}
+
+/// The largest rectangle with red corners anywhere, and the pairs of red tiles
+/// that made the largest one inside the loop so far, in the order they were
+/// found (so the last one is the largest)
+fn find_largest(reds: &[Tile]) -> (usize, Vec<(usize, usize)>) {
+    let floor = Floor::new(reds);
+
+    let mut largest_anywhere = 0;
+    let mut largest = 0;
+    let mut bests = vec![];
+    for i in 0..reds.len() - 1 {
+        for j in i + 1..reds.len() {
+            let (first, second) = (&reds[i], &reds[j]);
+            let area = area(first, second);
+            largest_anywhere = largest_anywhere.max(area);
+            if area > largest && floor.contains(first, second) {
+                largest = area;
+                bests.push((i, j));
+            }
+        }
+    }
+
+    (largest_anywhere, bests)
+}
+
+/// One `x,y` red tile per line. Whether they make a loop is up to `validate`.
+pub fn parse(data: &str) -> Result<Vec<Tile>, ParseError> {
+    let mut reds = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let Some((x, y)) = line.split_once(",") else {
+            return Err(ParseError::new(i + 1, ErrorKind::Missing("','")));
+        };
+        reds.push(Tile {
+            x: number(i + 1, x)?,
+            y: number(i + 1, y)?,
+        });
+    }
+    Ok(reds)
+}
+
+/// A rectangle that was the largest inside the loop when it was found
+#[derive(Serialize)]
+struct Largest<'a> {
+    area: usize,
+    corners: [&'a Tile; 2],
+}
+
+/// Returns the index pairs of every rectangle that became the new largest
+/// inside the loop, in the order they were found
+fn process(reds: &[Tile], report: &mut Report) -> Vec<(usize, usize)> {
+    let start = Instant::now();
+    let (largest_anywhere, bests) = find_largest(reds);
+    let elapsed = start.elapsed();
+
+    let mut largest = 0;
+    for (i, j) in &bests {
+        let (first, second) = (&reds[*i], &reds[*j]);
+        largest = area(first, second);
+        report.say(format!("new largest ({largest}): {first} and {second}"));
+        report.detail(Largest {
+            area: largest,
+            corners: [first, second],
+        });
+    }
+
+    // both parts come out of the same search
+    report.answer(1, largest_anywhere, elapsed);
+    report.answer(2, largest, elapsed);
+
+    bests
+}
+
+/// Draws each tile as a unit square, scaled so the longer side of the floor is
+/// `SVG_SIZE` pixels. Rectangles that were the best at some point fade out the
+/// earlier they were found; the final best is outlined in blue.
+fn to_svg(reds: &[Tile], bests: &[(usize, usize)]) -> String {
+    const SVG_SIZE: f64 = 1000.0;
+
+    let left = reds.iter().map(|t| t.x).min().unwrap_or(0);
+    let right = reds.iter().map(|t| t.x).max().unwrap_or(0) + 1;
+    let top = reds.iter().map(|t| t.y).min().unwrap_or(0);
+    let bottom = reds.iter().map(|t| t.y).max().unwrap_or(0) + 1;
+    let (width, height) = (right - left, bottom - top);
+    let scale = SVG_SIZE / width.max(height) as f64;
+    // keep lines a few pixels wide whatever the coordinate range
+    let stroke = 2.0 / scale;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"{left} {top} {width} {height}\">\n",
+        width as f64 * scale,
+        height as f64 * scale,
+    );
+
+    let points: Vec<String> = reds
+        .iter()
+        .map(|t| format!("{},{}", t.x as f64 + 0.5, t.y as f64 + 0.5))
+        .collect();
+    out.push_str(&format!(
+        "  <polygon points=\"{}\" fill=\"lightgreen\" stroke=\"red\" stroke-width=\"{stroke}\"/>\n",
+        points.join(" ")
+    ));
+    for t in reds {
+        out.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"red\"/>\n",
+            t.x, t.y
+        ));
+    }
+
+    for (age, (i, j)) in bests.iter().enumerate() {
+        let (first, second) = (&reds[*i], &reds[*j]);
+        let is_final = age == bests.len() - 1;
+        let opacity = (age + 1) as f64 / bests.len() as f64;
+        out.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-opacity=\"{opacity:.2}\"/>\n",
+            first.x.min(second.x),
+            first.y.min(second.y),
+            first.x.abs_diff(second.x) + 1,
+            first.y.abs_diff(second.y) + 1,
+            if is_final { "blue" } else { "black" },
+            if is_final { stroke * 2.0 } else { stroke },
+        ));
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::generate;
+    use aoc::rng::Rng;
+    use proptest::prelude::*;
+
+    /// Marks every tile on the loop, floods the outside, and then checks every
+    /// tile of every rectangle
+    fn brute_force(reds: &[Tile]) -> usize {
+        // shifted by one so there's always a row and column of outside around it
+        let width = reds.iter().map(|t| t.x).max().unwrap() + 3;
+        let height = reds.iter().map(|t| t.y).max().unwrap() + 3;
+        let mut inside = vec![vec![true; width]; height];
+        let mut edge = vec![vec![false; width]; height];
+        for i in 0..reds.len() {
+            let (a, b) = (&reds[i], &reds[(i + 1) % reds.len()]);
+            for row in edge
+                .iter_mut()
+                .take(a.y.max(b.y) + 2)
+                .skip(a.y.min(b.y) + 1)
+            {
+                for cell in row.iter_mut().take(a.x.max(b.x) + 2).skip(a.x.min(b.x) + 1) {
+                    *cell = true;
+                }
+            }
+        }
+        let mut todo: Vec<(usize, usize)> = vec![(0, 0)];
+        inside[0][0] = false;
+        while let Some((r, c)) = todo.pop() {
+            for (nr, nc) in [
+                (r + 1, c),
+                (r.wrapping_sub(1), c),
+                (r, c + 1),
+                (r, c.wrapping_sub(1)),
+            ] {
+                if nr < height && nc < width && !edge[nr][nc] && inside[nr][nc] {
+                    inside[nr][nc] = false;
+                    todo.push((nr, nc));
+                }
+            }
+        }
+
+        let mut largest = 0;
+        for first in reds {
+            for second in reds {
+                let rows = first.y.min(second.y) + 1..=first.y.max(second.y) + 1;
+                let columns = first.x.min(second.x) + 1..=first.x.max(second.x) + 1;
+                if rows.clone().all(|r| columns.clone().all(|c| inside[r][c])) {
+                    largest = largest.max(area(first, second));
+                }
+            }
+        }
+        largest
+    }
+
+    /// Polygons like `aoc gen 9` makes, but small enough to brute force
+    fn polygons() -> impl Strategy<Value = Vec<Tile>> {
+        (any::<u64>(), 1..8_usize).prop_map(|(seed, columns)| {
+            generate::polygon(&mut Rng::new(seed), columns, 5, 40)
+                .into_iter()
+                .map(|(x, y)| Tile { x, y })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_examples() {
+        for (path, expected) in [("test.txt", 24), ("test2.txt", 21), ("test3.txt", 35)] {
+            let reds = parse(&fs::read_to_string(path).unwrap()).unwrap();
+            assert_eq!(expected, brute_force(&reds), "{path} by brute force");
+            let (_, bests) = find_largest(&reds);
+            let (i, j) = bests.last().unwrap();
+            assert_eq!(expected, area(&reds[*i], &reds[*j]), "{path}");
+        }
+    }
+
+    /// The red tiles from a line of corners like "0,0 2,0 2,2 0,2"
+    fn tiles(corners: &str) -> Vec<Tile> {
+        parse(&corners.replace(' ', "\n")).unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let square = tiles("0,0 2,0 2,2 0,2");
+        assert_eq!(Ok(Winding::Clockwise), validate(&square));
+        let reversed: Vec<Tile> = square.into_iter().rev().collect();
+        assert_eq!(Ok(Winding::Counterclockwise), validate(&reversed));
+
+        let cases = [
+            ("0,0 2,0 2,2", "need at least 4 red tiles, found 3"),
+            (
+                "0,0 2,0 2,2 1,2 0,1",
+                "line 4: 1,2 isn't in the same row or column as line 5: 0,1",
+            ),
+            (
+                "0,0 2,0 2,2 4,2 4,4 2,4 2,2 0,2",
+                "line 7: 2,2 duplicates line 3",
+            ),
+            (
+                "0,0 1,0 2,0 2,2 0,2",
+                "line 2: 1,0 is in the middle of a straight run",
+            ),
+            (
+                // the two long edges cross in the middle
+                "0,1 3,1 3,2 2,2 2,0 1,0 1,3 0,3",
+                "line 1: edge 0,1 -> 3,1 crosses or touches edge 2,2 -> 2,0 from line 4",
+            ),
+            (
+                // the notch comes up to the top edge without crossing it
+                "0,0 1,0 1,4 2,4 2,0 3,0 3,4 0,4",
+                "line 2: edge 1,0 -> 1,4 crosses or touches edge 3,4 -> 0,4 from line 7",
+            ),
+        ];
+        for (corners, expected) in cases {
+            let problems = validate(&tiles(corners)).unwrap_err();
+            assert_eq!(expected, problems[0], "{corners}");
+        }
+    }
+
+    #[test]
+    fn test_svg() {
+        let reds = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        let (_, bests) = find_largest(&reds);
+        let svg = to_svg(&reds, &bests);
+
+        let polygon = svg.lines().find(|l| l.contains("<polygon")).unwrap();
+        let points = polygon.split('"').nth(1).unwrap();
+        assert_eq!(reds.len(), points.split(' ').count());
+
+        let (i, j) = bests.last().unwrap();
+        let (first, second) = (&reds[*i], &reds[*j]);
+        let outline = svg
+            .lines()
+            .rfind(|l| l.contains("stroke=\"blue\""))
+            .unwrap();
+        let expected = format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+            first.x.min(second.x),
+            first.y.min(second.y),
+            first.x.abs_diff(second.x) + 1,
+            first.y.abs_diff(second.y) + 1,
+        );
+        assert!(outline.trim().starts_with(&expected), "{outline}");
+        assert_eq!(24, area(first, second));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn matches_brute_force(reds in polygons()) {
+            prop_assert!(validate(&reds).is_ok(), "generated polygon is valid");
+            let (_, bests) = find_largest(&reds);
+            let largest = bests.last().map_or(0, |(i, j)| area(&reds[*i], &reds[*j]));
+            prop_assert_eq!(brute_force(&reds), largest);
+        }
+    }
+}
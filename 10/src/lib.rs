@@ -0,0 +1,1161 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use std::{fmt, path::PathBuf, thread};
+
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, number};
+use aoc::report::{Format, Report};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+
+/// AOC 25 day 10
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// How to find the fewest presses for each machine
+    #[arg(long, value_enum, default_value_t = Solver::Elimination)]
+    solver: Solver,
+
+    /// List how many times to press each button, for every machine
+    #[arg(long)]
+    plan: bool,
+
+    /// How many machines to solve at once (defaults to the number of CPUs)
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Report the solver's statistics for each machine
+    #[arg(long)]
+    verbose: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Solver {
+    /// Gaussian elimination, then search over the free buttons only
+    Elimination,
+    /// Depth-first search over press counts for every button
+    Search,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(
+        10,
+        &args.inputs,
+        args.format,
+        None,
+        parse,
+        |machines, report| process(machines, &args, report),
+    );
+}
+
+#[derive(Debug)]
+pub struct Machine {
+    pub lights: Vec<bool>,
+    pub buttons: Vec<Vec<usize>>,
+    pub joltage_targets: Vec<usize>,
+}
+
+/// How matching the lights went for one machine
+#[derive(Debug, PartialEq)]
+enum Lights {
+    /// The fewest presses that match them
+    Presses(usize),
+    Impossible,
+    /// More than `MAX_BUTTONS` buttons, so too many to try
+    Skipped,
+}
+
+struct MachineResult {
+    lights: Lights,
+    plan: Result<PressPlan, Infeasible>,
+    lights_elapsed: Duration,
+    plan_elapsed: Duration,
+    stats: Option<Stats>,
+}
+
+fn solve_machine(machine: &Machine, solver: Solver) -> MachineResult {
+    let start = Instant::now();
+    let lights = if machine.buttons.len() > MAX_BUTTONS {
+        Lights::Skipped
+    } else {
+        solve_lights(machine).map_or(Lights::Impossible, Lights::Presses)
+    };
+    let lights_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let mut stats = None;
+    let plan = check_feasible(machine).and_then(|_| {
+        let plan = match solver {
+            Solver::Elimination => {
+                let mut elimination = EliminationStats::default();
+                let plan = solve_by_elimination(machine, &mut elimination);
+                stats = Some(Stats::Elimination(elimination));
+                plan
+            }
+            Solver::Search => {
+                let maps = map_joltages_to_button_sequence(machine);
+                let mut memo = SearchMemo::new();
+                let plan = find_min_by_joltage(machine, &maps, &State::start(machine), &mut memo);
+                stats = Some(Stats::Search(memo.stats));
+                plan
+            }
+        };
+        plan.ok_or(Infeasible::NoWholeSolution)
+    });
+
+    MachineResult {
+        lights,
+        plan,
+        lights_elapsed,
+        plan_elapsed: start.elapsed(),
+        stats,
+    }
+}
+
+/// Machines are independent, so each worker just takes the next unsolved one
+/// until there are none left. Results are stored by machine index so they can
+/// be reported in input order however the work got split up.
+fn solve_all(machines: &[Machine], solver: Solver, jobs: usize) -> Vec<MachineResult> {
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<MachineResult>>> =
+        machines.iter().map(|_| Mutex::new(None)).collect();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, machines.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let m = next.fetch_add(1, Ordering::Relaxed);
+                    if m >= machines.len() {
+                        break;
+                    }
+                    let result = solve_machine(&machines[m], solver);
+                    *results[m].lock().unwrap() = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|r| r.into_inner().unwrap().unwrap())
+        .collect()
+}
+
+/// What happened with one machine, for JSON output
+#[derive(Serialize)]
+struct MachineDetail {
+    machine: usize,
+    seconds: f64,
+    /// The fewest presses to match the lights, if they can be
+    lights: Option<usize>,
+    /// How many times to press each button, in input order, to hit the
+    /// joltage targets
+    presses: Option<Vec<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    infeasible: Option<String>,
+    /// Set if the plan doesn't actually reach the targets, which is a bug
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn process(machines: Vec<Machine>, args: &Args, report: &mut Report) {
+    let jobs = args
+        .jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let results = solve_all(&machines, args.solver, jobs);
+
+    // each part's time is summed over the machines, so with several jobs it
+    // can come to more than the time the whole run took
+    let mut lights_answer = 0;
+    let mut lights_elapsed = Duration::ZERO;
+    let mut answer = 0;
+    let mut plan_elapsed = Duration::ZERO;
+    let mut skipped = vec![];
+    let mut infeasible = vec![];
+    let mut wrong = vec![];
+    for (m, (machine, result)) in machines.iter().zip(results).enumerate() {
+        let elapsed = result.lights_elapsed + result.plan_elapsed;
+        lights_elapsed += result.lights_elapsed;
+        plan_elapsed += result.plan_elapsed;
+        report.say(format!(
+            "Machine {m} ({:?}) took {elapsed:?}",
+            machine.joltage_targets
+        ));
+
+        match result.lights {
+            Lights::Presses(press_count) => {
+                report.say(format!(" lights -> {press_count}"));
+                lights_answer += press_count;
+            }
+            Lights::Impossible => report.say(" lights -> impossible"),
+            Lights::Skipped => {
+                report.say(format!(
+                    " lights -> skipped, {} buttons is more than {MAX_BUTTONS}",
+                    machine.buttons.len()
+                ));
+                skipped.push(m);
+            }
+        }
+
+        if args.verbose
+            && let Some(stats) = &result.stats
+        {
+            report.say(format!(" {stats}"));
+        }
+
+        let mut error = None;
+        match &result.plan {
+            Ok(plan) if !plan.verify(machine) => {
+                let why = format!("plan {plan} doesn't reach {:?}", machine.joltage_targets);
+                report.say(format!(" -> {why}"));
+                error = Some(why);
+                wrong.push(m);
+            }
+            Ok(plan) => {
+                report.say(format!(" -> {}", plan.total()));
+                if args.plan {
+                    report.say(format!(" plan: {}", plan.describe(machine)));
+                }
+                answer += plan.total();
+            }
+            Err(reason) => {
+                report.say(format!(" -> infeasible: {reason}"));
+                infeasible.push(m);
+            }
+        }
+        report.say("");
+
+        report.detail(MachineDetail {
+            machine: m,
+            seconds: elapsed.as_secs_f64(),
+            lights: match result.lights {
+                Lights::Presses(press_count) => Some(press_count),
+                Lights::Impossible | Lights::Skipped => None,
+            },
+            presses: result.plan.as_ref().ok().map(|plan| plan.presses.clone()),
+            infeasible: result.plan.as_ref().err().map(|reason| reason.to_string()),
+            error,
+        });
+    }
+    if !wrong.is_empty() {
+        report.fail(format!(
+            "The plans for machines {wrong:?} don't reach their joltage targets"
+        ));
+        return;
+    }
+    if !skipped.is_empty() {
+        report.warn(format!(
+            "Left machines {skipped:?} out of the lights total, as they have more than {MAX_BUTTONS} buttons"
+        ));
+    }
+    if !infeasible.is_empty() {
+        report.warn(format!(
+            "Left infeasible machines {infeasible:?} out of the total"
+        ));
+    }
+    report.answer(1, lights_answer, lights_elapsed);
+    report.answer(2, answer, plan_elapsed);
+}
+
+/// Why a machine's joltage targets can't be hit
+#[derive(Debug, PartialEq)]
+enum Infeasible {
+    /// A button is wired to a joltage counter the machine doesn't have
+    UnknownJoltage { button: usize, joltage_index: usize },
+    /// A counter needs to go up but no button is wired to it
+    UnreachableJoltage { joltage_index: usize, target: usize },
+    /// The targets contradict each other, whatever the press counts
+    Inconsistent,
+    /// The equations have solutions, but none in non-negative whole presses
+    NoWholeSolution,
+}
+
+impl fmt::Display for Infeasible {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Infeasible::UnknownJoltage {
+                button,
+                joltage_index,
+            } => write!(
+                f,
+                "button {button} is wired to missing joltage {joltage_index}"
+            ),
+            Infeasible::UnreachableJoltage {
+                joltage_index,
+                target,
+            } => write!(
+                f,
+                "no button is wired to joltage {joltage_index}, which needs to reach {target}"
+            ),
+            Infeasible::Inconsistent => write!(f, "the joltage equations contradict each other"),
+            Infeasible::NoWholeSolution => {
+                write!(f, "no whole number of presses hits the targets")
+            }
+        }
+    }
+}
+
+/// The cheap checks that don't need a search
+fn check_feasible(machine: &Machine) -> Result<(), Infeasible> {
+    let joltage_count = machine.joltage_targets.len();
+    for (button, joltages) in machine.buttons.iter().enumerate() {
+        if let Some(joltage_index) = joltages.iter().find(|j| **j >= joltage_count) {
+            return Err(Infeasible::UnknownJoltage {
+                button,
+                joltage_index: *joltage_index,
+            });
+        }
+    }
+
+    let maps = map_joltages_to_buttons(machine);
+    for map in maps {
+        let target = machine.joltage_targets[map.joltage_index];
+        if map.button_indexes.is_empty() && target > 0 {
+            return Err(Infeasible::UnreachableJoltage {
+                joltage_index: map.joltage_index,
+                target,
+            });
+        }
+    }
+
+    if ReducedSystem::new(machine).is_none() {
+        return Err(Infeasible::Inconsistent);
+    }
+
+    Ok(())
+}
+
+/// Each light is an equation over GF(2): the buttons that toggle it, XORed
+/// together, have to equal whether it should end up on. Pressing a button twice
+/// undoes it, so each button is pressed either once or not at all.
+///
+/// Returns the fewest presses, or `None` if the lights can't be matched.
+fn solve_lights(machine: &Machine) -> Option<usize> {
+    let button_count = machine.buttons.len();
+    assert!(button_count <= MAX_BUTTONS, "too many buttons to try");
+    let target_bit = 1u64 << button_count;
+
+    // one row per light: a bit per button that toggles it, then the target bit
+    let mut rows: Vec<u64> = machine
+        .lights
+        .iter()
+        .enumerate()
+        .map(|(light, on)| {
+            let mut row = if *on { target_bit } else { 0 };
+            for (b, button) in machine.buttons.iter().enumerate() {
+                if button.contains(&light) {
+                    row |= 1 << b;
+                }
+            }
+            row
+        })
+        .collect();
+
+    let mut pivots = vec![];
+    for col in 0..button_count {
+        let rank = pivots.len();
+        let Some(found) = (rank..rows.len()).find(|r| rows[*r] & (1 << col) != 0) else {
+            continue;
+        };
+        rows.swap(rank, found);
+        let pivot_row = rows[rank];
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != rank && *row & (1 << col) != 0 {
+                *row ^= pivot_row;
+            }
+        }
+        pivots.push(col);
+    }
+
+    // anything left is 0 = target, which had better be 0 = 0
+    if rows[pivots.len()..].iter().any(|row| row & target_bit != 0) {
+        return None;
+    }
+
+    // every choice of free buttons gives exactly one solution, so the solutions
+    // are one particular solution plus the null space; try them all
+    let free: Vec<usize> = (0..button_count).filter(|c| !pivots.contains(c)).collect();
+    let mut best: Option<usize> = None;
+    for choice in 0..1u64 << free.len() {
+        let mut presses = 0u64;
+        for (i, f) in free.iter().enumerate() {
+            if choice & (1 << i) != 0 {
+                presses |= 1 << f;
+            }
+        }
+        for (row, pivot) in rows.iter().zip(pivots.iter()) {
+            let parity = (row & target_bit != 0) ^ ((row & presses).count_ones() % 2 == 1);
+            if parity {
+                presses |= 1 << pivot;
+            }
+        }
+        let count = presses.count_ones() as usize;
+        if best.is_none_or(|best_count| count < best_count) {
+            best = Some(count);
+        }
+    }
+    best
+}
+
+/// A machine's joltage equations (one row per joltage, one column per button,
+/// then the target) in reduced row echelon form. This is elimination over the
+/// rationals, but rows are scaled up rather than divided so everything stays in
+/// integers; each row is some multiple of its rational equivalent.
+struct ReducedSystem {
+    /// Only the non-zero rows; row `i` has its pivot in column `pivots[i]`
+    rows: Vec<Vec<i64>>,
+    pivots: Vec<usize>,
+    /// Buttons without a pivot, whose press counts we have to search for
+    free: Vec<usize>,
+}
+
+impl ReducedSystem {
+    /// Returns `None` if the equations are inconsistent
+    fn new(machine: &Machine) -> Option<ReducedSystem> {
+        let button_count = machine.buttons.len();
+        let mut rows: Vec<Vec<i64>> = machine
+            .joltage_targets
+            .iter()
+            .enumerate()
+            .map(|(joltage_index, target)| {
+                let mut row: Vec<i64> = machine
+                    .buttons
+                    .iter()
+                    .map(|button| button.contains(&joltage_index) as i64)
+                    .collect();
+                row.push(*target as i64);
+                row
+            })
+            .collect();
+
+        let mut pivots = vec![];
+        for col in 0..button_count {
+            let rank = pivots.len();
+            let Some(found) = (rank..rows.len()).find(|r| rows[*r][col] != 0) else {
+                continue;
+            };
+            rows.swap(rank, found);
+            if rows[rank][col] < 0 {
+                rows[rank].iter_mut().for_each(|v| *v = -*v);
+            }
+
+            let pivot_row = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r != rank && row[col] != 0 {
+                    let (p, q) = (pivot_row[col], row[col]);
+                    for c in 0..=button_count {
+                        row[c] = row[c] * p - pivot_row[c] * q;
+                    }
+                    reduce_row(row);
+                }
+            }
+            pivots.push(col);
+        }
+
+        // anything left is 0 = target, which had better be 0 = 0
+        if rows[pivots.len()..]
+            .iter()
+            .any(|row| row[button_count] != 0)
+        {
+            return None;
+        }
+        rows.truncate(pivots.len());
+
+        let free = (0..button_count).filter(|c| !pivots.contains(c)).collect();
+        Some(ReducedSystem { rows, pivots, free })
+    }
+
+    /// Fills in the pivot buttons' presses from the free buttons' presses;
+    /// fails if any of them comes out negative or fractional
+    fn solve_pivots(&self, presses: &mut [usize]) -> bool {
+        let target_col = presses.len();
+        for (row, pivot) in self.rows.iter().zip(self.pivots.iter()) {
+            let mut rhs = row[target_col];
+            for f in self.free.iter() {
+                rhs -= row[*f] * presses[*f] as i64;
+            }
+            if rhs < 0 || rhs % row[*pivot] != 0 {
+                return false;
+            }
+            presses[*pivot] = (rhs / row[*pivot]) as usize;
+        }
+        true
+    }
+}
+
+fn reduce_row(row: &mut [i64]) {
+    let divisor = row.iter().fold(0, |acc, v| gcd(acc, v.unsigned_abs()));
+    if divisor > 1 {
+        row.iter_mut().for_each(|v| *v /= divisor as i64);
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Returns the plan with the fewest total presses that hits the joltage targets
+/// exactly, or `None` if they can't be hit
+/// What `solve_by_elimination` had to search through
+#[derive(Clone, Debug, Default)]
+struct EliminationStats {
+    /// Buttons left without a pivot
+    free: usize,
+    /// Calls to `search_free_buttons`, i.e. partial choices of free presses
+    nodes: usize,
+}
+
+fn solve_by_elimination(machine: &Machine, stats: &mut EliminationStats) -> Option<PressPlan> {
+    let system = ReducedSystem::new(machine)?;
+    stats.free = system.free.len();
+
+    // no button can be pressed more often than its smallest joltage target
+    let bounds: Vec<usize> = machine
+        .buttons
+        .iter()
+        .map(|button| {
+            button
+                .iter()
+                .map(|j| machine.joltage_targets[*j])
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut presses = vec![0; machine.buttons.len()];
+    let mut best = None;
+    search_free_buttons(&system, &bounds, &mut presses, 0, &mut best, stats);
+    best.map(|(_, presses)| PressPlan { presses })
+}
+
+// Branch and bound over the free buttons: pivot presses are never negative, so
+// once the free presses alone reach the best total there's no point going on.
+fn search_free_buttons(
+    system: &ReducedSystem,
+    bounds: &[usize],
+    presses: &mut [usize],
+    depth: usize,
+    best: &mut Option<(usize, Vec<usize>)>,
+    stats: &mut EliminationStats,
+) {
+    stats.nodes += 1;
+    let best_total = best.as_ref().map_or(usize::MAX, |(total, _)| *total);
+
+    if depth == system.free.len() {
+        if system.solve_pivots(presses) {
+            let total = presses.iter().sum();
+            if total < best_total {
+                *best = Some((total, presses.to_vec()));
+            }
+        }
+        return;
+    }
+
+    let free_so_far: usize = system.free[..depth].iter().map(|f| presses[*f]).sum();
+    let button = system.free[depth];
+    for press_count in 0..=bounds[button] {
+        let best_total = best.as_ref().map_or(usize::MAX, |(total, _)| *total);
+        if free_so_far + press_count >= best_total {
+            break;
+        }
+        presses[button] = press_count;
+        search_free_buttons(system, bounds, presses, depth + 1, best, stats);
+    }
+    presses[button] = 0;
+}
+
+/// How many times to press each button, indexed like `Machine::buttons`
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct PressPlan {
+    presses: Vec<usize>,
+}
+
+impl PressPlan {
+    fn total(&self) -> usize {
+        self.presses.iter().sum()
+    }
+
+    fn combine(&self, other: &PressPlan) -> PressPlan {
+        PressPlan {
+            presses: self
+                .presses
+                .iter()
+                .zip(other.presses.iter())
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+
+    fn subtract(&self, other: &PressPlan) -> PressPlan {
+        PressPlan {
+            presses: self
+                .presses
+                .iter()
+                .zip(other.presses.iter())
+                .map(|(a, b)| a - b)
+                .collect(),
+        }
+    }
+
+    /// Replays the plan from all zeros and checks it lands exactly on the targets
+    fn verify(&self, machine: &Machine) -> bool {
+        let mut state = Some(State::start(machine));
+        for (button_index, presses) in self.presses.iter().enumerate() {
+            state = state.and_then(|s| do_press(machine, &s, button_index, *presses));
+        }
+        state.is_some_and(|s| s.joltages == machine.joltage_targets)
+    }
+
+    /// e.g. `(1,3)x5 (2,3)x1`, skipping buttons that aren't pressed
+    fn describe(&self, machine: &Machine) -> String {
+        let pressed: Vec<String> = self
+            .presses
+            .iter()
+            .zip(machine.buttons.iter())
+            .filter(|(presses, _)| **presses > 0)
+            .map(|(presses, button)| {
+                let joltages: Vec<String> = button.iter().map(|j| j.to_string()).collect();
+                format!("({})x{presses}", joltages.join(","))
+            })
+            .collect();
+        pressed.join(" ")
+    }
+}
+
+impl fmt::Display for PressPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.presses)
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct State {
+    joltages: Vec<usize>,
+    press_count: usize,
+    plan: PressPlan,
+}
+
+impl State {
+    fn start(machine: &Machine) -> State {
+        State {
+            joltages: vec![0; machine.joltage_targets.len()],
+            press_count: 0,
+            plan: PressPlan {
+                presses: vec![0; machine.buttons.len()],
+            },
+        }
+    }
+}
+
+/// What we know about finishing the search from a given state
+#[derive(Clone, Debug)]
+enum Memo {
+    /// The cheapest way to finish, as presses on top of the state's own
+    Best(PressPlan),
+    /// Finishing takes at least this many more presses, or can't be done
+    AtLeast(usize),
+}
+
+#[derive(Clone, Debug, Default)]
+struct SearchStats {
+    hits: usize,
+    misses: usize,
+    entries: usize,
+    pruned: usize,
+}
+
+/// Whichever solver ran, for `--verbose`
+#[derive(Debug)]
+enum Stats {
+    Elimination(EliminationStats),
+    Search(SearchStats),
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stats::Elimination(stats) => write!(
+                f,
+                "elimination: {} free buttons, {} search nodes",
+                stats.free, stats.nodes
+            ),
+            Stats::Search(stats) => write!(
+                f,
+                "memo: {} hits, {} misses, {} entries; {} pruned by bound",
+                stats.hits, stats.misses, stats.entries, stats.pruned
+            ),
+        }
+    }
+}
+
+const MEMO_LIMIT: usize = 4_000_000;
+
+/// Different press orders reach the same joltages, so remember how each
+/// (remaining map count, joltages) pair worked out. `best_total` is the best
+/// complete plan so far, which anything that can't beat it gets pruned against.
+struct SearchMemo {
+    table: HashMap<(usize, Vec<usize>), Memo>,
+    best_total: usize,
+    stats: SearchStats,
+}
+
+impl SearchMemo {
+    fn new() -> SearchMemo {
+        SearchMemo {
+            table: HashMap::new(),
+            best_total: usize::MAX,
+            stats: SearchStats::default(),
+        }
+    }
+
+    /// Stops taking new entries once it's holding `MEMO_LIMIT`, so a search that
+    /// rarely revisits states doesn't just eat all the memory
+    fn remember(&mut self, key: (usize, Vec<usize>), known: Memo) {
+        if let Some(entry) = self.table.get_mut(&key) {
+            *entry = known;
+        } else if self.table.len() < MEMO_LIMIT {
+            self.table.insert(key, known);
+            self.stats.entries += 1;
+        }
+    }
+
+    /// Whether the state can't possibly beat the best plan so far. Each press
+    /// adds at most 1 to any joltage, so it takes at least as many more presses
+    /// as the biggest remaining difference.
+    fn can_prune(&mut self, machine: &Machine, state: &State) -> bool {
+        let lower_bound = state
+            .joltages
+            .iter()
+            .zip(machine.joltage_targets.iter())
+            .map(|(joltage, target)| target - joltage)
+            .max()
+            .unwrap_or(0);
+        let prune = state.press_count + lower_bound >= self.best_total;
+        if prune {
+            self.stats.pruned += 1;
+        }
+        prune
+    }
+}
+
+// Permute all possible combinations of button presses.
+// Order doesn't matter; just counts (per button).
+// Never any need to press a button more than (target - current).
+// A button might be pressed anywhere from 0..(target-current).
+//
+// So e.g. 5 buttons with a target of 200 gives us 200^5 = 320B possible combinations.
+// But since we know we're evaluating all possibilities,
+// and not trying to find the shortest path first, recursive DFS would work.
+// We don't really need to know the "path" - just the button count that gets us there.
+//
+// Idea credit to michelkraemer at https://www.reddit.com/r/adventofcode/comments/1pity70/comment/nt9h7qw/
+//
+// Only plans that beat `memo.best_total` are returned, so a `None` just means
+// nothing better was found from here.
+fn find_min_by_joltage(
+    machine: &Machine,
+    maps: &[JoltageButtonMap],
+    state: &State,
+    memo: &mut SearchMemo,
+) -> Option<PressPlan> {
+    if maps.is_empty() {
+        if state.joltages == machine.joltage_targets && state.press_count < memo.best_total {
+            memo.best_total = state.press_count;
+            return Some(state.plan.clone());
+        } else {
+            return None;
+        }
+    }
+
+    if memo.can_prune(machine, state) {
+        return None;
+    }
+
+    let budget = memo.best_total - state.press_count;
+    let key = (maps.len(), state.joltages.clone());
+    if let Some(known) = memo.table.get(&key) {
+        match known {
+            Memo::Best(rest) if rest.total() < budget => {
+                memo.stats.hits += 1;
+                let plan = state.plan.combine(rest);
+                memo.best_total = plan.total();
+                return Some(plan);
+            }
+            Memo::Best(_) => {
+                memo.stats.hits += 1;
+                return None;
+            }
+            Memo::AtLeast(needed) if *needed >= budget => {
+                memo.stats.hits += 1;
+                return None;
+            }
+            // we know less than we need to; search again with the bigger budget
+            Memo::AtLeast(_) => {}
+        }
+    }
+    memo.stats.misses += 1;
+
+    let found = find_min_from_maps(machine, maps, state, memo);
+
+    // Anything pruned below here couldn't beat what was found, so a plan
+    // that was found is the best one. Otherwise all we know is that it takes
+    // at least the budget we had.
+    let known = match &found {
+        Some(plan) => Memo::Best(plan.subtract(&state.plan)),
+        None => Memo::AtLeast(budget),
+    };
+    memo.remember(key, known);
+
+    found
+}
+
+fn find_min_from_maps(
+    machine: &Machine,
+    maps: &[JoltageButtonMap],
+    state: &State,
+    memo: &mut SearchMemo,
+) -> Option<PressPlan> {
+    // println!(" state: {state:?}");
+    // println!(" maps: {maps:?}");
+    let mut remaining_maps = maps.to_vec();
+    let current_map = remaining_maps.remove(0);
+
+    if current_map.button_indexes.is_empty() {
+        find_min_by_joltage(machine, &remaining_maps, state, memo)
+    } else {
+        find_min_by_buttons(
+            machine,
+            state,
+            &current_map.button_indexes,
+            &current_map.joltage_index,
+            &remaining_maps,
+            memo,
+        )
+    }
+}
+
+fn find_min_by_buttons(
+    machine: &Machine,
+    state: &State,
+    button_indexes: &[usize],
+    joltage_index: &usize,
+    remaining_maps: &[JoltageButtonMap],
+    memo: &mut SearchMemo,
+) -> Option<PressPlan> {
+    if memo.can_prune(machine, state) {
+        return None;
+    }
+
+    // println!("  buttons: {button_indexes:?}");
+    let joltage_target_value = machine.joltage_targets[*joltage_index];
+    let joltage_diff = joltage_target_value - state.joltages[*joltage_index];
+
+    let mut remaining_buttons = button_indexes.to_vec();
+    let current_button = remaining_buttons.remove(0);
+    // println!("  current_button: {current_button:?}");
+
+    // just an optimization; not strictly necessary
+    let mut min_press_to_test = 0;
+    if remaining_buttons.is_empty() {
+        min_press_to_test = joltage_diff;
+    }
+
+    let mut best_so_far: Option<PressPlan> = None;
+    for press_count in min_press_to_test..=joltage_diff {
+        if let Some(new_state) = do_press(machine, state, current_button, press_count) {
+            let answer = if remaining_buttons.is_empty() {
+                find_min_by_joltage(machine, remaining_maps, &new_state, memo)
+            } else {
+                find_min_by_buttons(
+                    machine,
+                    &new_state,
+                    &remaining_buttons,
+                    joltage_index,
+                    remaining_maps,
+                    memo,
+                )
+            };
+            if let Some(answer) = answer
+                && best_so_far
+                    .as_ref()
+                    .is_none_or(|best| answer.total() < best.total())
+            {
+                best_so_far = Some(answer);
+                // println!("   best_so_far: {best_so_far}");
+            }
+        }
+    }
+
+    // println!("  returning");
+    best_so_far
+}
+
+fn do_press(
+    machine: &Machine,
+    state: &State,
+    button_index: usize,
+    presses: usize,
+) -> Option<State> {
+    let mut new_state = state.clone();
+
+    new_state.press_count += presses;
+    new_state.plan.presses[button_index] += presses;
+
+    for joltage_index in machine.buttons[button_index].iter() {
+        new_state.joltages[*joltage_index] += presses;
+
+        if new_state.joltages[*joltage_index] > machine.joltage_targets[*joltage_index] {
+            return None;
+        }
+    }
+
+    Some(new_state)
+}
+
+#[derive(Clone, Debug)]
+struct JoltageButtonMap {
+    joltage_index: usize,
+    button_indexes: Vec<usize>,
+}
+
+/// One map per joltage, listing every button wired to it
+fn map_joltages_to_buttons(machine: &Machine) -> Vec<JoltageButtonMap> {
+    let mut maps: Vec<JoltageButtonMap> = vec![];
+    for joltage_index in 0..machine.joltage_targets.len() {
+        maps.push(JoltageButtonMap {
+            joltage_index,
+            button_indexes: vec![],
+        });
+    }
+
+    for button_index in 0..machine.buttons.len() {
+        for joltage_index in machine.buttons[button_index].iter() {
+            maps[*joltage_index].button_indexes.push(button_index);
+        }
+    }
+
+    maps
+}
+
+fn map_joltages_to_button_sequence(machine: &Machine) -> Vec<JoltageButtonMap> {
+    let mut maps = map_joltages_to_buttons(machine);
+    // println!("{maps:#?}");
+
+    maps.sort_by(|a, b| {
+        a.button_indexes
+            .len()
+            .cmp(&b.button_indexes.len())
+            .then_with(|| {
+                machine.joltage_targets[b.joltage_index]
+                    .cmp(&machine.joltage_targets[a.joltage_index])
+            })
+    });
+    // maps.sort_by(|a, b| machine.joltage_targets[a.joltage_index].cmp(&machine.joltage_targets[b.joltage_index]));
+
+    let mut buttons_used: Vec<usize> = Vec::new();
+    for map in &mut maps {
+        map.button_indexes.retain(|idx| {
+            if buttons_used.contains(idx) {
+                false
+            } else {
+                buttons_used.push(*idx);
+                true
+            }
+        });
+    }
+    // println!("{maps:#?}");
+
+    maps
+}
+
+/// The most buttons `solve_lights` takes on. It tries every choice of the
+/// buttons its equations leave free, which could be all of them, so this keeps
+/// that to about a million; machines with more are skipped for the lights.
+const MAX_BUTTONS: usize = 20;
+
+/// One machine per line: `[.##.] (3) (1,3) {3,5,4}`, i.e. the lights, then
+/// each button's wiring, then the joltage targets
+pub fn parse(data: &str) -> Result<Vec<Machine>, ParseError> {
+    let mut machines: Vec<Machine> = vec![];
+    for (i, line) in data.lines().enumerate() {
+        let line_no = i + 1;
+        let mut spec = line.split_whitespace();
+
+        let Some(lights) = spec.next() else {
+            return Err(ParseError::new(line_no, ErrorKind::Missing("lights")));
+        };
+        let lights = bracketed(line_no, lights, '[', ']')?
+            .chars()
+            .map(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(ParseError::new(line_no, ErrorKind::UnexpectedChar(c))),
+            })
+            .collect::<Result<Vec<bool>, ParseError>>()?;
+
+        let mut spec: Vec<&str> = spec.collect();
+        let Some(joltages) = spec.pop() else {
+            return Err(ParseError::new(line_no, ErrorKind::Missing("joltages")));
+        };
+        let joltages = numbers(line_no, bracketed(line_no, joltages, '{', '}')?)?;
+
+        let buttons = spec
+            .iter()
+            .map(|button| numbers(line_no, bracketed(line_no, button, '(', ')')?))
+            .collect::<Result<Vec<Vec<usize>>, ParseError>>()?;
+
+        machines.push(Machine {
+            lights,
+            buttons,
+            joltage_targets: joltages,
+        })
+    }
+    Ok(machines)
+}
+
+/// What's between `open` and `close`, which have to be at either end of `token`
+fn bracketed(line: usize, token: &str, open: char, close: char) -> Result<&str, ParseError> {
+    token
+        .strip_prefix(open)
+        .and_then(|rest| rest.strip_suffix(close))
+        .ok_or_else(|| {
+            let why = format!("expected {open}...{close}, not {token:?}");
+            ParseError::new(line, ErrorKind::Invalid(why))
+        })
+}
+
+fn numbers(line: usize, list: &str) -> Result<Vec<usize>, ParseError> {
+    list.split(",").map(|s| number(line, s)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::generate;
+    use aoc::rng::Rng;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Machines like `aoc gen 10` makes, but small enough to search
+    fn machines() -> impl Strategy<Value = Machine> {
+        any::<u64>().prop_map(|seed| {
+            let line = generate::machine(&mut Rng::new(seed), 2..8, 10);
+            parse(&line).unwrap().remove(0)
+        })
+    }
+
+    #[test]
+    fn test_example() {
+        let machines = parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        for solver in [Solver::Elimination, Solver::Search] {
+            let results: Vec<MachineResult> = machines
+                .iter()
+                .map(|machine| solve_machine(machine, solver))
+                .collect();
+            let lights: usize = results
+                .iter()
+                .map(|r| match r.lights {
+                    Lights::Presses(press_count) => press_count,
+                    _ => panic!("the example's lights can all be matched"),
+                })
+                .sum();
+            let presses: usize = results
+                .iter()
+                .map(|r| r.plan.as_ref().unwrap().total())
+                .sum();
+            assert_eq!(7, lights);
+            assert_eq!(33, presses);
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let machine = &parse("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}").unwrap()[0];
+        let plan = PressPlan {
+            presses: vec![1, 3, 0, 3, 1, 2],
+        };
+        assert!(plan.verify(machine));
+
+        let short = PressPlan {
+            presses: vec![1, 3, 0, 3, 1, 1],
+        };
+        assert!(!short.verify(machine), "joltage 0 only gets to 2");
+        let over = PressPlan {
+            presses: vec![1, 3, 0, 3, 2, 2],
+        };
+        assert!(!over.verify(machine), "joltage 0 goes past 3");
+    }
+
+    #[test]
+    fn test_too_many_buttons() {
+        let line = |buttons: usize| format!("[#] {} {{1}}", "(0) ".repeat(buttons));
+        let most = &parse(&line(MAX_BUTTONS)).unwrap()[0];
+        assert_eq!(
+            Lights::Presses(1),
+            solve_machine(most, Solver::Elimination).lights
+        );
+
+        // only the lights are skipped; the joltages still get solved
+        let too_many = &parse(&line(MAX_BUTTONS + 1)).unwrap()[0];
+        let result = solve_machine(too_many, Solver::Elimination);
+        assert_eq!(Lights::Skipped, result.lights);
+        assert_eq!(1, result.plan.unwrap().total());
+    }
+
+    /// Tries pressing every set of buttons once
+    fn brute_force_lights(machine: &Machine) -> Option<usize> {
+        (0..1u64 << machine.buttons.len())
+            .filter(|presses| {
+                machine.lights.iter().enumerate().all(|(light, on)| {
+                    let toggles = machine
+                        .buttons
+                        .iter()
+                        .enumerate()
+                        .filter(|(b, button)| presses & (1 << b) != 0 && button.contains(&light))
+                        .count();
+                    (toggles % 2 == 1) == *on
+                })
+            })
+            .map(|presses| presses.count_ones() as usize)
+            .min()
+    }
+
+    proptest! {
+        #[test]
+        fn lights_match_brute_force(mut machine in machines(), lights in any::<u64>()) {
+            // any pattern at all, not just ones the buttons are known to make
+            for (i, on) in machine.lights.iter_mut().enumerate() {
+                *on = lights & (1 << i) != 0;
+            }
+            prop_assert_eq!(
+                brute_force_lights(&machine),
+                solve_lights(&machine)
+            );
+        }
+
+        #[test]
+        fn solvers_agree(machine in machines()) {
+            let elimination = solve_machine(&machine, Solver::Elimination);
+            let search = solve_machine(&machine, Solver::Search);
+            for plan in [&elimination.plan, &search.plan].into_iter().flatten() {
+                prop_assert!(plan.verify(&machine), "{} replays to the targets", plan);
+            }
+            prop_assert_eq!(
+                elimination.plan.map(|plan| plan.total()),
+                search.plan.map(|plan| plan.total())
+            );
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use aoc::parse::{ErrorKind, ParseError};
+use num_bigint::BigUint;
+use std::collections::{HashMap, VecDeque};
+use std::mem;
+
+/// The most devices `count_paths_from` can count paths through, since a device
+/// can end up with a count for every subset of them
+pub const MAX_VIA: usize = 15;
+
+/// The devices, with each name interned to an id so that the graph itself is
+/// just vectors indexed by id
+pub struct DeviceGraph {
+    pub names: Vec<String>,
+    ids: HashMap<String, usize>,
+    /// Whether each device had a line of its own, rather than only ever being
+    /// someone's output
+    pub defined: Vec<bool>,
+    pub outputs: Vec<Vec<usize>>,
+}
+
+impl DeviceGraph {
+    /// One `name: output output ...` line per device, each device only once
+    pub fn parse(data: &str) -> Result<DeviceGraph, ParseError> {
+        let mut graph = DeviceGraph {
+            names: vec![],
+            ids: HashMap::new(),
+            defined: vec![],
+            outputs: vec![],
+        };
+        for (i, line) in data.lines().enumerate() {
+            let mut fields = line.split_whitespace();
+            let name = fields
+                .next()
+                .and_then(|field| field.strip_suffix(":"))
+                .filter(|name| !name.is_empty() && !name.contains(":"))
+                .ok_or(ParseError::new(i + 1, ErrorKind::Missing("device name")))?;
+            let id = graph.intern(name);
+            if graph.defined[id] {
+                let why = format!("{name} is already defined");
+                return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+            }
+            graph.defined[id] = true;
+            let mut outputs = vec![];
+            for name in fields {
+                if name.contains(":") {
+                    return Err(ParseError::new(i + 1, ErrorKind::UnexpectedChar(':')));
+                }
+                outputs.push(graph.intern(name));
+            }
+            graph.outputs[id] = outputs;
+        }
+        Ok(graph)
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.defined.push(false);
+        self.outputs.push(vec![]);
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Kahn's algorithm: repeatedly take a device that nothing left points to.
+    /// Returns `None` if there's a cycle, since then we run out of those early.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut inputs = vec![0; self.len()];
+        for outputs in &self.outputs {
+            for output in outputs {
+                inputs[*output] += 1;
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..self.len()).filter(|id| inputs[*id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for output in &self.outputs[id] {
+                inputs[*output] -= 1;
+                if inputs[*output] == 0 {
+                    ready.push_back(*output);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Counts the paths from `source` to every device, split by which of `via`
+    /// each path passes through: `counts[device][&visited]`, where `visited` is
+    /// a bitmask over `via`. Only the masks some path actually gets to are
+    /// there, so `counts[device].get(&(1 << via.len()) - 1)` is the paths that go
+    /// through all of them, in any order, if there are any.
+    ///
+    /// Devices are visited in topological order, so by the time we get to one
+    /// we've already counted every path into it.
+    pub fn count_paths_from(&self, source: usize, via: &[usize]) -> Vec<HashMap<usize, BigUint>> {
+        assert!(via.len() <= MAX_VIA, "too many devices to pass through");
+        // a device listed twice sets both its bits
+        let via_bit = |id: usize| -> usize {
+            via.iter()
+                .enumerate()
+                .filter(|(_, v)| **v == id)
+                .fold(0, |bits, (i, _)| bits | 1 << i)
+        };
+
+        let order = self.topological_order().expect("device graph has a cycle");
+        let mut counts: Vec<HashMap<usize, BigUint>> = vec![HashMap::new(); self.len()];
+        counts[source].insert(via_bit(source), BigUint::from(1u32));
+
+        for id in order {
+            // no loops, so none of its outputs is the device itself
+            let here = mem::take(&mut counts[id]);
+            for output in &self.outputs[id] {
+                let bit = via_bit(*output);
+                for (visited, count) in &here {
+                    *counts[*output].entry(visited | bit).or_default() += count;
+                }
+            }
+            counts[id] = here;
+        }
+
+        counts
+    }
+
+    /// Counts the paths from every device to `sink`; the same as
+    /// `count_paths_from`, but working backwards from the end of the order
+    pub fn count_paths_to(&self, sink: usize) -> Vec<BigUint> {
+        let order = self.topological_order().expect("device graph has a cycle");
+        let mut counts = vec![BigUint::ZERO; self.len()];
+        counts[sink] = BigUint::from(1u32);
+
+        for id in order.into_iter().rev() {
+            if id == sink {
+                continue;
+            }
+            let mut total = BigUint::ZERO;
+            for output in &self.outputs[id] {
+                total += &counts[*output];
+            }
+            counts[id] = total;
+        }
+
+        counts
+    }
+
+    /// How many of the paths from `source` to `sink` pass through each device:
+    /// the paths into it times the paths out of it
+    pub fn count_paths_through(&self, source: usize, sink: usize) -> Vec<BigUint> {
+        let from = self.count_paths_from(source, &[]);
+        let to = self.count_paths_to(sink);
+        from.iter()
+            .zip(to.iter())
+            .map(|(f, t)| f.get(&0).map_or(BigUint::ZERO, |f| f * t))
+            .collect()
+    }
+
+    /// The devices that every path from `source` to `target` passes through,
+    /// starting with `target` and working back to `source`. Empty if `target`
+    /// can't be reached.
+    ///
+    /// In topological order, a device's immediate dominator is the nearest
+    /// common dominator of everything feeding it (Cooper, Harvey and Kennedy's
+    /// "intersect"), and those have all been worked out already.
+    pub fn dominators(&self, source: usize, target: usize) -> Vec<usize> {
+        let order = self.topological_order().expect("device graph has a cycle");
+        let mut position = vec![0; self.len()];
+        for (i, id) in order.iter().enumerate() {
+            position[*id] = i;
+        }
+        let mut inputs: Vec<Vec<usize>> = vec![vec![]; self.len()];
+        for (id, outputs) in self.outputs.iter().enumerate() {
+            for output in outputs {
+                inputs[*output].push(id);
+            }
+        }
+
+        let mut idom: Vec<Option<usize>> = vec![None; self.len()];
+        idom[source] = Some(source);
+        for id in order.into_iter().skip(position[source] + 1) {
+            let mut found: Option<usize> = None;
+            for input in inputs[id].iter().filter(|i| idom[**i].is_some()) {
+                found = Some(match found {
+                    None => *input,
+                    Some(mut a) => {
+                        let mut b = *input;
+                        while a != b {
+                            while position[a] > position[b] {
+                                a = idom[a].unwrap();
+                            }
+                            while position[b] > position[a] {
+                                b = idom[b].unwrap();
+                            }
+                        }
+                        a
+                    }
+                });
+            }
+            idom[id] = found;
+        }
+
+        let mut chain = vec![];
+        if idom[target].is_none() {
+            return chain;
+        }
+        let mut id = target;
+        while id != source {
+            chain.push(id);
+            id = idom[id].unwrap();
+        }
+        chain.push(source);
+        chain
+    }
+
+    /// Graphviz DOT, with the `highlight` devices filled in
+    pub fn to_dot(&self, highlight: &[&str]) -> String {
+        let mut out = String::from("digraph devices {\n");
+        for name in highlight {
+            if self.ids.contains_key(*name) {
+                out.push_str(&format!(
+                    "  {} [style=filled, fillcolor=gold];\n",
+                    quote(name)
+                ));
+            }
+        }
+        for (id, outputs) in self.outputs.iter().enumerate() {
+            for output in outputs {
+                out.push_str(&format!(
+                    "  {} -> {};\n",
+                    quote(&self.names[id]),
+                    quote(&self.names[*output])
+                ));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// A DOT ID for any device name, which can have quotes or backslashes in it
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::fs;
+
+    /// Every path from `source` to `sink`, as the list of devices along it
+    fn all_paths(graph: &DeviceGraph, source: usize, sink: usize) -> Vec<Vec<usize>> {
+        let mut paths = vec![];
+        let mut todo = vec![vec![source]];
+        while let Some(path) = todo.pop() {
+            let last = *path.last().unwrap();
+            if last == sink {
+                paths.push(path.clone());
+            }
+            for output in &graph.outputs[last] {
+                let mut longer = path.clone();
+                longer.push(*output);
+                todo.push(longer);
+            }
+        }
+        paths
+    }
+
+    /// Devices `d0` (the source) to `d{n-1}` (the sink), each only feeding
+    /// higher-numbered ones so there can't be a cycle, written out in a
+    /// shuffled order so ids don't come out in topological order
+    fn dags() -> impl Strategy<Value = String> {
+        (2..9_usize)
+            .prop_flat_map(|n| {
+                let edges = prop::collection::vec(any::<bool>(), n * (n - 1) / 2);
+                let order = Just((0..n).collect::<Vec<usize>>()).prop_shuffle();
+                (Just(n), edges, order)
+            })
+            .prop_map(|(n, edges, order)| {
+                let mut outputs = vec![vec![]; n];
+                let pairs = (0..n).flat_map(|a| (a + 1..n).map(move |b| (a, b)));
+                for ((a, b), present) in pairs.zip(edges) {
+                    if present {
+                        outputs[a].push(format!("d{b}"));
+                    }
+                }
+                order
+                    .into_iter()
+                    .map(|d| format!("d{d}: {}\n", outputs[d].join(" ")))
+                    .collect()
+            })
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        assert_eq!(
+            ParseError::new(2, ErrorKind::Missing("device name")),
+            DeviceGraph::parse("a: b\nb c").err().unwrap()
+        );
+        assert!(matches!(
+            DeviceGraph::parse("a: b\na: c").err().unwrap().kind,
+            ErrorKind::Invalid(_)
+        ));
+        assert_eq!(
+            ParseError::new(1, ErrorKind::UnexpectedChar(':')),
+            DeviceGraph::parse("a: b:").err().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_counts_are_sparse() {
+        // a chain through MAX_VIA waypoints only ever gets to one mask per device
+        let data: String = (0..=MAX_VIA)
+            .map(|d| format!("d{d}: d{}\n", d + 1))
+            .collect();
+        let graph = DeviceGraph::parse(&data).unwrap();
+        let via: Vec<usize> = (1..=MAX_VIA)
+            .map(|d| graph.id(&format!("d{d}")).unwrap())
+            .collect();
+        let counts = graph.count_paths_from(graph.id("d0").unwrap(), &via);
+        assert!(counts.iter().all(|masks| masks.len() == 1));
+        let sink = graph.id(&format!("d{}", MAX_VIA + 1)).unwrap();
+        assert_eq!(
+            Some(&BigUint::from(1u32)),
+            counts[sink].get(&((1 << MAX_VIA) - 1))
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let graph = DeviceGraph::parse(&fs::read_to_string("test.txt").unwrap()).unwrap();
+        let dot = graph.to_dot(&["you", "svr", "out"]);
+        assert_eq!(17, dot.lines().filter(|l| l.contains(" -> ")).count());
+        let highlights: Vec<&str> = dot.lines().filter(|l| l.contains("fillcolor")).collect();
+        assert_eq!(
+            vec![
+                "  \"you\" [style=filled, fillcolor=gold];",
+                "  \"out\" [style=filled, fillcolor=gold];",
+            ],
+            highlights
+        );
+
+        let graph = DeviceGraph::parse("a\"b: c\\d").unwrap();
+        assert!(graph.to_dot(&[]).contains(r#"  "a\"b" -> "c\\d";"#));
+    }
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(text in any::<String>()) {
+            let _ = DeviceGraph::parse(&text);
+        }
+
+        #[test]
+        fn counts_match_brute_force(data in dags(), via in prop::collection::vec(0..9_usize, 0..3)) {
+            let graph = DeviceGraph::parse(&data).unwrap();
+            let (source, sink) = (graph.id("d0").unwrap(), graph.id(&format!("d{}", graph.len() - 1)).unwrap());
+            let via: Vec<usize> = via.into_iter().filter_map(|d| graph.id(&format!("d{d}"))).collect();
+            let paths = all_paths(&graph, source, sink);
+
+            let through_via = paths.iter().filter(|path| via.iter().all(|v| path.contains(v))).count();
+            let counts = graph.count_paths_from(source, &via);
+            let all = (1 << via.len()) - 1;
+            prop_assert_eq!(BigUint::from(through_via), counts[sink].get(&all).cloned().unwrap_or_default());
+
+            prop_assert_eq!(BigUint::from(paths.len()), graph.count_paths_to(sink)[source].clone());
+
+            let through = graph.count_paths_through(source, sink);
+            for (id, count) in through.into_iter().enumerate() {
+                let expected = paths.iter().filter(|path| path.contains(&id)).count();
+                prop_assert_eq!(BigUint::from(expected), count, "through {}", graph.names[id]);
+            }
+
+            let mut dominators = graph.dominators(source, sink);
+            dominators.sort();
+            let expected: Vec<usize> = if paths.is_empty() {
+                vec![]
+            } else {
+                (0..graph.len()).filter(|id| paths.iter().all(|path| path.contains(id))).collect()
+            };
+            prop_assert_eq!(expected, dominators);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use aoc::day;
+use aoc::parse::ParseError;
+use aoc::report::{Format, Report};
+use clap::Parser;
+use graph::{DeviceGraph, MAX_VIA};
+use num_bigint::BigUint;
+use serde::Serialize;
+use std::{fs, path::PathBuf, time::Instant};
+
+pub mod graph;
+
+/// AOC 25 day 11
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Device the paths start at
+    #[arg(long, default_value = "svr")]
+    from: String,
+
+    /// Device the paths end at
+    #[arg(long, default_value = "out")]
+    to: String,
+
+    /// Devices every path has to pass through, in any order (comma-separated;
+    /// pass an empty string for none)
+    #[arg(long, value_delimiter = ',', default_value = "dac,fft")]
+    via: Vec<String>,
+
+    /// Write the devices to this path as a Graphviz DOT graph
+    #[arg(long)]
+    dot: Option<PathBuf>,
+
+    /// List the devices every path from the start to the end goes through
+    #[arg(long)]
+    dominators: bool,
+
+    /// List how many paths from the start to the end go through each device
+    #[arg(long)]
+    through: bool,
+}
+
+/// The devices the puzzle cares about, which get picked out in the DOT output
+const NOTABLE_DEVICES: [&str; 5] = ["you", "svr", "out", "dac", "fft"];
+
+pub fn run() {
+    let args = Args::parse();
+    let single = args.dot.as_ref().map(|_| "--dot");
+
+    day::run(
+        11,
+        &args.inputs,
+        args.format,
+        single,
+        parse,
+        |graph, report| {
+            if let Err(e) = process(graph, &args, report) {
+                report.fail(format!("{}: {e}", report.input));
+            }
+        },
+    );
+}
+
+pub fn parse(data: &str) -> Result<DeviceGraph, ParseError> {
+    DeviceGraph::parse(data)
+}
+
+/// The devices every path goes through, for JSON output
+#[derive(Serialize)]
+struct Dominators<'a> {
+    dominators: Vec<&'a str>,
+}
+
+/// How many paths go through one device, for JSON output
+#[derive(Serialize)]
+struct Through<'a> {
+    device: &'a str,
+    /// As a string, like the answer, since it can be huge
+    paths: String,
+}
+
+/// The path count for a query that isn't one of the puzzle's parts, for JSON
+/// output
+#[derive(Serialize)]
+struct Paths<'a> {
+    from: &'a str,
+    to: &'a str,
+    via: Vec<&'a str>,
+    /// As a string, like the answer, since it can be huge
+    paths: String,
+}
+
+/// Which part of the puzzle the flags ask for: part 1 is the paths from `you`
+/// to `out`, and part 2 from `svr` to `out` via `dac` and `fft`. Anything else
+/// is just a count of paths.
+fn part(args: &Args, waypoints: &[&str]) -> Option<u8> {
+    let mut via = waypoints.to_vec();
+    via.sort();
+    match (args.from.as_str(), args.to.as_str(), via.as_slice()) {
+        ("you", "out", []) => Some(1),
+        ("svr", "out", ["dac", "fft"]) => Some(2),
+        _ => None,
+    }
+}
+
+/// Fails if the start isn't a device, there are too many devices to go via,
+/// or the devices have a loop
+fn process(graph: DeviceGraph, args: &Args, report: &mut Report) -> Result<(), String> {
+    if let Some(path) = &args.dot
+        && let Err(e) = fs::write(path, graph.to_dot(&NOTABLE_DEVICES))
+    {
+        return Err(format!("Failed to write {}: {e}", path.display()));
+    }
+
+    let Some(source) = graph.id(&args.from) else {
+        return Err(format!("{} isn't defined as a device", args.from));
+    };
+    let waypoints: Vec<&str> = args
+        .via
+        .iter()
+        .map(String::as_str)
+        .filter(|v| !v.is_empty())
+        .collect();
+    if waypoints.len() > MAX_VIA {
+        return Err(format!(
+            "Paths can go via at most {MAX_VIA} devices, not {}",
+            waypoints.len()
+        ));
+    }
+
+    let start = Instant::now();
+    let checks = validate(&graph, source, &args.to);
+    for device in &checks.dangling {
+        report.warn(format!(
+            "{device} is an output but isn't defined as a device"
+        ));
+    }
+    for device in &checks.unreachable {
+        report.warn(format!("{device} can't be reached from {}", args.from));
+    }
+    if let Some(cycle) = &checks.cycle {
+        return Err(format!(
+            "The devices aren't acyclic: {}",
+            cycle.join(" -> ")
+        ));
+    }
+
+    // a sink or waypoint that isn't in the graph at all just means no paths
+    let sink = graph.id(&args.to);
+    let via: Option<Vec<usize>> = waypoints.iter().map(|v| graph.id(v)).collect();
+    let count = match (sink, via) {
+        (Some(sink), Some(via)) => {
+            let all = (1 << via.len()) - 1;
+            let mut counts = graph.count_paths_from(source, &via);
+            counts[sink].remove(&all).unwrap_or_default()
+        }
+        _ => BigUint::ZERO,
+    };
+
+    match part(args, &waypoints) {
+        Some(part) => report.answer(part, count, start.elapsed()),
+        None => {
+            report.say(format!(
+                "paths from {} to {} via [{}]: {count}",
+                args.from,
+                args.to,
+                waypoints.join(",")
+            ));
+            report.detail(Paths {
+                from: &args.from,
+                to: &args.to,
+                via: waypoints,
+                paths: count.to_string(),
+            });
+        }
+    }
+
+    if let Some(sink) = sink {
+        if args.dominators {
+            let names: Vec<&str> = graph
+                .dominators(source, sink)
+                .into_iter()
+                .map(|id| graph.names[id].as_str())
+                .collect();
+            report.say(format!("dominators of {}: {}", args.to, names.join(" <- ")));
+            report.detail(Dominators { dominators: names });
+        }
+        if args.through {
+            let through = graph.count_paths_through(source, sink);
+            let mut busiest: Vec<usize> = (0..graph.len())
+                .filter(|id| through[*id] > BigUint::ZERO)
+                .collect();
+            busiest.sort_by(|a, b| through[*b].cmp(&through[*a]));
+            for id in busiest {
+                report.say(format!("{} {}", graph.names[id], through[id]));
+                report.detail(Through {
+                    device: &graph.names[id],
+                    paths: through[id].to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct GraphReport {
+    /// Devices forming a loop, starting and ending with the same one
+    cycle: Option<Vec<String>>,
+    /// Outputs (other than the sink) that have no line of their own
+    dangling: Vec<String>,
+    /// Defined devices that no path from the source gets to
+    unreachable: Vec<String>,
+}
+
+fn validate(graph: &DeviceGraph, source: usize, sink: &str) -> GraphReport {
+    let mut dangling: Vec<String> = (0..graph.len())
+        .filter(|id| !graph.defined[*id] && graph.names[*id] != sink)
+        .map(|id| graph.names[id].clone())
+        .collect();
+    dangling.sort();
+
+    let mut reachable = vec![false; graph.len()];
+    reachable[source] = true;
+    let mut todo = vec![source];
+    while let Some(id) = todo.pop() {
+        for output in &graph.outputs[id] {
+            if !reachable[*output] {
+                reachable[*output] = true;
+                todo.push(*output);
+            }
+        }
+    }
+    let mut unreachable: Vec<String> = (0..graph.len())
+        .filter(|id| graph.defined[*id] && !reachable[*id])
+        .map(|id| graph.names[id].clone())
+        .collect();
+    unreachable.sort();
+
+    GraphReport {
+        cycle: find_cycle(graph),
+        dangling,
+        unreachable,
+    }
+}
+
+/// Depth-first search that keeps its own stack, so deep graphs can't overflow
+/// the real one. Meeting a device that's still on the stack means everything
+/// from there up is a loop.
+fn find_cycle(graph: &DeviceGraph) -> Option<Vec<String>> {
+    let mut finished = vec![false; graph.len()];
+    let mut on_stack = vec![false; graph.len()];
+
+    for start in 0..graph.len() {
+        if finished[start] {
+            continue;
+        }
+        on_stack[start] = true;
+        let mut stack: Vec<(usize, usize)> = vec![(start, 0)];
+        while let Some((id, next)) = stack.last().copied() {
+            let outputs = &graph.outputs[id];
+            if next == outputs.len() {
+                stack.pop();
+                on_stack[id] = false;
+                finished[id] = true;
+                continue;
+            }
+            stack.last_mut().unwrap().1 += 1;
+
+            let output = outputs[next];
+            if on_stack[output] {
+                let from = stack.iter().position(|(d, _)| *d == output).unwrap();
+                let mut cycle: Vec<String> = stack[from..]
+                    .iter()
+                    .map(|(d, _)| graph.names[*d].clone())
+                    .collect();
+                cycle.push(graph.names[output].clone());
+                return Some(cycle);
+            }
+            if !finished[output] {
+                on_stack[output] = true;
+                stack.push((output, 0));
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,353 @@
+// Knuth's Algorithm X, with the "dancing links" trick for undoing each step:
+// https://arxiv.org/abs/cs/0011047
+//
+// Every node lives in the same set of vectors and is linked to its neighbours
+// by index. Nodes 1..=columns are the column headers and node 0 is the root,
+// which links together the headers of the columns that still need covering.
+//
+// A primary column can also need covering more than once, as in Knuth's
+// Algorithm M. Its rows get tried in order, and once one is in, the rows
+// above it are out, so the same set of rows never comes up in another order.
+
+use std::time::Instant;
+
+const ROOT: usize = 0;
+
+/// How often (in search steps) to look at the clock
+const STEPS_PER_CLOCK_CHECK: usize = 4096;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// The indexes of the rows in the first solution found
+    Solved(Vec<usize>),
+    NoSolution,
+    OutOfTime,
+}
+
+/// An exact cover problem: pick a set of rows that covers every primary column
+/// exactly as many times as it needs, and every secondary column at most once
+pub struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The header node of each node's column (headers point at themselves)
+    column: Vec<usize>,
+    /// Which row each node belongs to (meaningless for headers)
+    row: Vec<usize>,
+    /// How many rows are still in each column, indexed by header node
+    size: Vec<usize>,
+    /// How many more of those rows each column needs, indexed by header node
+    needed: Vec<usize>,
+    row_count: usize,
+    deadline: Option<Instant>,
+    steps: usize,
+}
+
+impl Dlx {
+    /// Columns `0..counts.len()` are primary, and column `c` of those has to
+    /// be covered `counts[c]` times, which mustn't be 0; the `secondary`
+    /// columns come after them
+    pub fn new(counts: &[usize], secondary: usize) -> Dlx {
+        assert!(!counts.contains(&0), "every primary column needs covering");
+        let primary = counts.len();
+        let headers = primary + secondary + 1;
+        let mut dlx = Dlx {
+            left: (0..headers).collect(),
+            right: (0..headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec![usize::MAX; headers],
+            size: vec![0; headers],
+            needed: [&[0], counts, &vec![1; secondary][..]].concat(),
+            row_count: 0,
+            deadline: None,
+            steps: 0,
+        };
+
+        // only the primary headers go in the root's list; secondary headers
+        // just link to themselves, so covering them never unlinks anything
+        // from it and the search never has to choose them
+        for header in 1..=primary {
+            dlx.left[header] = header - 1;
+            dlx.right[header - 1] = header;
+        }
+        dlx.right[primary] = ROOT;
+        dlx.left[ROOT] = primary;
+
+        dlx
+    }
+
+    /// Adds a row covering the given columns, and returns its index
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let row = self.row_count;
+        self.row_count += 1;
+
+        let mut first: Option<usize> = None;
+        for column in columns {
+            let header = column + 1;
+            let node = self.column.len();
+
+            self.column.push(header);
+            self.row.push(row);
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[header] = node;
+            self.size[header] += 1;
+
+            match first {
+                None => {
+                    self.left.push(node);
+                    self.right.push(node);
+                    first = Some(node);
+                }
+                Some(first) => {
+                    let last = self.left[first];
+                    self.left.push(last);
+                    self.right.push(first);
+                    self.right[last] = node;
+                    self.left[first] = node;
+                }
+            }
+        }
+
+        row
+    }
+
+    /// Finds the first solution, giving up once `deadline` (if any) has passed
+    pub fn solve_before(&mut self, deadline: Option<Instant>) -> Outcome {
+        self.deadline = deadline;
+        self.steps = 0;
+        let mut chosen = vec![];
+        match self.search(&mut chosen) {
+            Some(true) => Outcome::Solved(chosen),
+            Some(false) => Outcome::NoSolution,
+            None => Outcome::OutOfTime,
+        }
+    }
+
+    /// `None` means we ran out of time
+    fn search(&mut self, chosen: &mut Vec<usize>) -> Option<bool> {
+        if self.right[ROOT] == ROOT {
+            return Some(true);
+        }
+
+        self.steps += 1;
+        if self.steps.is_multiple_of(STEPS_PER_CLOCK_CHECK)
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() > deadline)
+        {
+            return None;
+        }
+
+        // the column with the fewest options left makes for the smallest tree
+        let mut best = self.right[ROOT];
+        let mut header = self.right[best];
+        while header != ROOT {
+            if self.options(header) < self.options(best) {
+                best = header;
+            }
+            header = self.right[header];
+        }
+
+        // try each row in turn as the next one in the column, and once it's
+        // been tried, leave it out
+        let mut found = Some(false);
+        let mut tried = vec![];
+        while self.size[best] >= self.needed[best] {
+            let node = self.down[best];
+            chosen.push(self.row[node]);
+            self.hide(node);
+            let mut other = self.right[node];
+            while other != node {
+                self.cover(self.column[other]);
+                other = self.right[other];
+            }
+            self.needed[best] -= 1;
+            if self.needed[best] == 0 {
+                self.cover(best);
+            }
+
+            found = self.search(chosen);
+
+            if self.needed[best] == 0 {
+                self.uncover(best);
+            }
+            self.needed[best] += 1;
+            let mut other = self.left[node];
+            while other != node {
+                self.uncover(self.column[other]);
+                other = self.left[other];
+            }
+            if found != Some(false) {
+                self.unhide(node);
+                break;
+            }
+            chosen.pop();
+            tried.push(node);
+        }
+        for node in tried.into_iter().rev() {
+            self.unhide(node);
+        }
+
+        found
+    }
+
+    /// How many ways there are of picking the column's next row
+    fn options(&self, header: usize) -> usize {
+        (self.size[header] + 1).saturating_sub(self.needed[header])
+    }
+
+    /// Takes the row that `node` is in out of all its columns
+    fn hide(&mut self, node: usize) {
+        let mut other = node;
+        loop {
+            let (up, down) = (self.up[other], self.down[other]);
+            self.down[up] = down;
+            self.up[down] = up;
+            self.size[self.column[other]] -= 1;
+            other = self.right[other];
+            if other == node {
+                break;
+            }
+        }
+    }
+
+    /// Exactly undoes `hide`
+    fn unhide(&mut self, node: usize) {
+        let mut other = self.left[node];
+        loop {
+            self.size[self.column[other]] += 1;
+            let (up, down) = (self.up[other], self.down[other]);
+            self.down[up] = other;
+            self.up[down] = other;
+            if other == node {
+                break;
+            }
+            other = self.left[other];
+        }
+    }
+
+    /// Takes the column out of the root's list, and every row that uses it out
+    /// of all the other columns
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+
+        let mut node = self.down[header];
+        while node != header {
+            let mut other = self.right[node];
+            while other != node {
+                let (up, down) = (self.up[other], self.down[other]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.column[other]] -= 1;
+                other = self.right[other];
+            }
+            node = self.down[node];
+        }
+    }
+
+    /// Exactly undoes `cover`, by walking everything in the opposite order
+    fn uncover(&mut self, header: usize) {
+        let mut node = self.up[header];
+        while node != header {
+            let mut other = self.left[node];
+            while other != node {
+                self.size[self.column[other]] += 1;
+                let (up, down) = (self.up[other], self.down[other]);
+                self.down[up] = other;
+                self.up[down] = other;
+                other = self.left[other];
+            }
+            node = self.up[node];
+        }
+
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knuth_example() {
+        // the example from the paper; the only solution is rows 0, 3 and 4
+        let mut dlx = Dlx::new(&[1; 7], 0);
+        dlx.add_row(&[2, 4, 5]);
+        dlx.add_row(&[0, 3, 6]);
+        dlx.add_row(&[1, 2, 5]);
+        dlx.add_row(&[0, 3]);
+        dlx.add_row(&[1, 6]);
+        dlx.add_row(&[3, 4, 6]);
+
+        let Outcome::Solved(mut solution) = dlx.solve_before(None) else {
+            panic!("there is a solution");
+        };
+        solution.sort();
+        assert_eq!(vec![0, 3, 4], solution);
+    }
+
+    #[test]
+    fn test_out_of_time() {
+        // pairing up an odd number of columns can't work, but takes a while to
+        // rule out
+        let mut dlx = Dlx::new(&[1; 13], 0);
+        for a in 0..13 {
+            for b in a + 1..13 {
+                dlx.add_row(&[a, b]);
+            }
+        }
+        let gone = Instant::now() - std::time::Duration::from_secs(1);
+        assert_eq!(Outcome::OutOfTime, dlx.solve_before(Some(gone)));
+        assert_eq!(Outcome::NoSolution, dlx.solve_before(None));
+    }
+
+    #[test]
+    fn test_secondary_columns() {
+        // column 1 is secondary, so it can be left uncovered but not doubled up
+        let mut dlx = Dlx::new(&[1], 1);
+        dlx.add_row(&[0]);
+        assert!(
+            matches!(dlx.solve_before(None), Outcome::Solved(_)),
+            "secondary column can stay empty"
+        );
+
+        let mut dlx = Dlx::new(&[1; 2], 1);
+        dlx.add_row(&[0, 2]);
+        dlx.add_row(&[1, 2]);
+        assert!(
+            dlx.solve_before(None) == Outcome::NoSolution,
+            "secondary column can't be covered twice"
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        // column 0 needs two rows, which between them can't double up on the
+        // secondary columns
+        let mut dlx = Dlx::new(&[2], 2);
+        dlx.add_row(&[0, 1]);
+        dlx.add_row(&[0, 1, 2]);
+        dlx.add_row(&[0, 2]);
+        assert_eq!(Outcome::Solved(vec![0, 2]), dlx.solve_before(None));
+
+        let mut dlx = Dlx::new(&[3, 1], 0);
+        dlx.add_row(&[0, 1]);
+        dlx.add_row(&[0, 1]);
+        dlx.add_row(&[0]);
+        assert_eq!(
+            Outcome::NoSolution,
+            dlx.solve_before(None),
+            "column 0 needs every row, which covers column 1 twice"
+        );
+    }
+}
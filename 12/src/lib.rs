@@ -0,0 +1,1454 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError, number};
+use aoc::report::{Format, Report};
+use clap::{Parser, ValueEnum};
+use dlx::{Dlx, Outcome};
+use serde::Serialize;
+use std::cell::Cell;
+use std::io::{self, IsTerminal};
+use std::iter::Peekable;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{fmt, fs, path::PathBuf};
+
+mod dlx;
+
+/// AOC 25 day 12
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// How to search for a packing
+    #[arg(long, value_enum, default_value_t = Solver::Backtrack)]
+    solver: Solver,
+
+    /// Give up searching a region after this many milliseconds (0 for never)
+    #[arg(long, default_value_t = 10_000)]
+    budget_ms: u64,
+
+    /// How many regions to solve at once (defaults to the number of CPUs)
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Draw how the presents go in each region they fit in
+    #[arg(long)]
+    show: bool,
+
+    /// Write the packings of the regions they fit in to this path as an SVG
+    #[arg(long)]
+    svg: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Solver {
+    /// Exact cover, with Algorithm X and dancing links
+    Dlx,
+    /// Try every shape at the first empty cell (or DLX, for regions more than
+    /// 64 wide)
+    Backtrack,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    let single = args.svg.as_ref().map(|_| "--svg");
+
+    day::run(
+        12,
+        &args.inputs,
+        args.format,
+        single,
+        parse,
+        |(shapes, regions), report| process(shapes, regions, &args, report),
+    );
+}
+
+/// One orientation of a shape as a bitmask per row, with bit `c` set if column
+/// `c` is filled. Shifting every row left by `c` moves it `c` columns right.
+#[derive(Debug)]
+struct Mask {
+    rows: Vec<u64>,
+    /// How far the filled cells reach, down and across
+    height: usize,
+    width: usize,
+}
+
+impl Mask {
+    fn new(orientation: &[Vec<bool>]) -> Mask {
+        let rows: Vec<u64> = orientation
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, filled)| **filled)
+                    .fold(0, |mask, (c, _)| mask | 1 << c)
+            })
+            .collect();
+        let height = rows.iter().rposition(|row| *row != 0).map_or(0, |r| r + 1);
+        let width = rows
+            .iter()
+            .map(|row| (u64::BITS - row.leading_zeros()) as usize)
+            .max()
+            .unwrap_or(0);
+        Mask {
+            rows,
+            height,
+            width,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Shape {
+    orientations: Vec<Vec<Vec<bool>>>,
+    masks: Vec<Mask>,
+    area: usize,
+}
+
+impl Shape {
+    fn new(shape: &[Vec<bool>]) -> Shape {
+        let orientations = Self::orientations(shape);
+        Shape {
+            masks: orientations.iter().map(|o| Mask::new(o)).collect(),
+            orientations,
+            area: shape
+                .iter()
+                .map(|row| row.iter().filter(|&cell| *cell).count())
+                .sum(),
+        }
+    }
+
+    /// How many more cells of one checkerboard colour than the other it covers,
+    /// which is the same wherever it goes and however it's turned
+    fn imbalance(&self) -> usize {
+        let Some(orientation) = self.orientations.first() else {
+            return 0;
+        };
+        let mut dark = 0isize;
+        for (r, row) in orientation.iter().enumerate() {
+            for (c, filled) in row.iter().enumerate() {
+                if *filled {
+                    dark += if (r + c) % 2 == 0 { 1 } else { -1 };
+                }
+            }
+        }
+        dark.unsigned_abs()
+    }
+
+    /// Every distinct rotation and reflection, each trimmed down to its filled
+    /// cells so that ones that only differ by where they sit in the original
+    /// grid come out the same
+    fn orientations(shape: &[Vec<bool>]) -> Vec<Vec<Vec<bool>>> {
+        let mut shape = normalize(shape);
+        let mut orientations = vec![];
+
+        for _ in 0..4 {
+            shape = rotate(shape);
+            orientations.push(shape.clone());
+        }
+
+        shape = flip(shape);
+        for _ in 0..4 {
+            shape = rotate(shape);
+            orientations.push(shape.clone());
+        }
+
+        orientations.sort();
+        orientations.dedup();
+
+        orientations
+    }
+}
+
+#[derive(Debug)]
+pub struct Region {
+    width: usize,
+    height: usize,
+    targets: Vec<usize>,
+}
+
+/// Where one present goes: which orientation of which shape, with the
+/// top-left of the orientation's grid at `row`,`col`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+struct Placement {
+    shape: usize,
+    orientation: usize,
+    row: usize,
+    col: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    Fits(&'static str, Vec<Placement>),
+    DoesNotFit(&'static str),
+    /// The search ran out of time
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Fits(why, _) => write!(f, "yes ({why})"),
+            Verdict::DoesNotFit(why) => write!(f, "no ({why})"),
+            Verdict::Unknown => write!(f, "unknown (out of time)"),
+        }
+    }
+}
+
+/// Lets a search give up after a while; it only actually looks at the clock
+/// every so often, since that's not free
+struct Deadline {
+    at: Option<Instant>,
+    checks: Cell<u32>,
+    passed: Cell<bool>,
+}
+
+impl Deadline {
+    fn new(budget: Option<Duration>) -> Deadline {
+        Deadline {
+            at: budget.map(|budget| Instant::now() + budget),
+            checks: Cell::new(0),
+            passed: Cell::new(false),
+        }
+    }
+
+    fn passed(&self) -> bool {
+        if self.passed.get() {
+            return true;
+        }
+        self.checks.set(self.checks.get().wrapping_add(1));
+        if self.checks.get().is_multiple_of(1024) && self.at.is_some_and(|at| Instant::now() > at) {
+            self.passed.set(true);
+        }
+        self.passed.get()
+    }
+}
+
+impl Region {
+    // turns out I have some prior experience with this type of problem!
+    // https://github.com/truist/puzzle/blob/master/solver.js
+    //
+    // The cheap checks go first, and settle most regions without searching.
+    fn can_fit(&self, shapes: &[Shape], solver: Solver, budget: Option<Duration>) -> Verdict {
+        if let Some(placements) = self.fits_in_blocks(shapes) {
+            return Verdict::Fits("one present per block", placements);
+        }
+
+        let mut min_area = 0;
+        for (shape_index, shape) in shapes.iter().enumerate() {
+            // saturates, since the counts could be anything
+            min_area = self.targets[shape_index]
+                .saturating_mul(shape.area)
+                .saturating_add(min_area);
+        }
+        if (self.width * self.height) < min_area {
+            return Verdict::DoesNotFit("not enough area");
+        }
+
+        if !self.colours_balance(shapes) {
+            return Verdict::DoesNotFit("checkerboard colouring");
+        }
+
+        let solver = match solver {
+            // each row of the board has to fit in a u64
+            Solver::Backtrack if self.width > u64::BITS as usize => Solver::Dlx,
+            solver => solver,
+        };
+        let deadline = Deadline::new(budget);
+        let placements = match solver {
+            Solver::Dlx => match self.pack_exactly(shapes, deadline.at) {
+                Ok(placements) => Some(placements),
+                Err(Outcome::OutOfTime) => return Verdict::Unknown,
+                Err(_) => None,
+            },
+            Solver::Backtrack => {
+                if self.targets.iter().all(|count| *count == 0) {
+                    return Verdict::Fits("nothing to fit", vec![]);
+                }
+                let mut board = vec![0u64; self.height];
+                let mut placed = vec![0; self.targets.len()];
+                // the top-left cell can be left empty too
+                let placements = self
+                    .try_shapes(&mut board, shapes, 0, 0, &mut placed, &deadline)
+                    .or_else(|| {
+                        self.try_next_location(&mut board, shapes, 0, 0, &mut placed, &deadline)
+                    });
+                if placements.is_none() && deadline.passed() {
+                    return Verdict::Unknown;
+                }
+                placements
+            }
+        };
+        match placements {
+            Some(placements) => Verdict::Fits("search", placements),
+            None => Verdict::DoesNotFit("search"),
+        }
+    }
+
+    /// Whether the region can be cut into square blocks, each big enough for
+    /// any present in any orientation, with at least one block per present;
+    /// if so, puts one present in the corner of each block
+    fn fits_in_blocks(&self, shapes: &[Shape]) -> Option<Vec<Placement>> {
+        let block = shapes
+            .iter()
+            .flat_map(|shape| shape.masks.iter())
+            .map(|mask| mask.height.max(mask.width))
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let across = self.width / block;
+        let presents = self
+            .targets
+            .iter()
+            .fold(0, |total: usize, count| total.saturating_add(*count));
+        if across * (self.height / block) < presents {
+            return None;
+        }
+
+        let presents = self
+            .targets
+            .iter()
+            .enumerate()
+            .flat_map(|(shape_index, count)| std::iter::repeat_n(shape_index, *count));
+        let placements = presents
+            .enumerate()
+            .map(|(i, shape_index)| Placement {
+                shape: shape_index,
+                orientation: 0,
+                row: i / across * block,
+                col: i % across * block,
+            })
+            .collect();
+        Some(placements)
+    }
+
+    /// Colour the region like a checkerboard. Wherever a present goes, it
+    /// covers the same number of cells of one colour more than the other (its
+    /// imbalance); just not always of the same colour. So see whether there's
+    /// a way of picking, per present, which colour gets the extra cells, that
+    /// doesn't need more cells of either colour than the region has.
+    fn colours_balance(&self, shapes: &[Shape]) -> bool {
+        let cells = self.width * self.height;
+        let (dark_cells, light_cells) = (cells.div_ceil(2) as isize, (cells / 2) as isize);
+
+        // only called once the area check has passed, so none of this overflows
+        let mut area = 0;
+        let mut imbalances = vec![];
+        for (shape_index, shape) in shapes.iter().enumerate() {
+            let count = self.targets[shape_index];
+            area += (count * shape.area) as isize;
+            let imbalance = shape.imbalance();
+            if imbalance > 0 && count > 0 {
+                imbalances.push((imbalance, count));
+            }
+        }
+
+        // every reachable total of (dark - light) over all the presents. The
+        // `count` presents of a shape add one of -count * k, (2 - count) * k, ...,
+        // count * k between them, for its imbalance k; so each total can come
+        // from any of a run of earlier totals 2k apart, which a running count of
+        // reachable totals every 2k finds in one pass per shape.
+        let max_total: usize = imbalances.iter().map(|(k, count)| k * count).sum();
+        let offset = max_total as isize;
+        let len = max_total * 2 + 1;
+        let mut reachable = vec![false; len];
+        reachable[max_total] = true;
+        for (k, count) in imbalances {
+            let step = 2 * k;
+            let mut so_far = vec![0; len];
+            for i in 0..len {
+                let before = if i >= step { so_far[i - step] } else { 0 };
+                so_far[i] = before + usize::from(reachable[i]);
+            }
+            let spread = k * count;
+            for (i, r) in reachable.iter_mut().enumerate() {
+                // the run is i - spread ..= i + spread, cut down to what's in range
+                let low = if i >= spread {
+                    i - spread
+                } else {
+                    i + (spread - i).div_ceil(step) * step
+                };
+                let high = if i + spread < len {
+                    i + spread
+                } else {
+                    i + spread - (i + spread - (len - 1)).div_ceil(step) * step
+                };
+                let before = if low >= step { so_far[low - step] } else { 0 };
+                *r = low <= high && so_far[high] > before;
+            }
+        }
+
+        // dark = (area + total) / 2 and light = (area - total) / 2
+        reachable
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| **r)
+            .map(|(i, _)| i as isize - offset)
+            .any(|total| area + total <= 2 * dark_cells && area - total <= 2 * light_cells)
+    }
+
+    /// Packing as exact cover: every shape with presents to place has a
+    /// primary column, which has to be covered once per present, and every
+    /// cell has a secondary column, which can be covered at most once (so
+    /// it's allowed to stay empty). Each row is the shape in one orientation
+    /// at one position.
+    fn pack_exactly(
+        &self,
+        shapes: &[Shape],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Placement>, Outcome> {
+        let wanted: Vec<usize> = (0..shapes.len())
+            .filter(|shape_index| self.targets[*shape_index] > 0)
+            .collect();
+        if wanted.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let counts: Vec<usize> = wanted.iter().map(|s| self.targets[*s]).collect();
+        let mut dlx = Dlx::new(&counts, self.width * self.height);
+        // what each row means, indexed by row
+        let mut rows = vec![];
+        for (column, shape_index) in wanted.iter().enumerate() {
+            // big regions take a while just to set up
+            if deadline.is_some_and(|deadline| Instant::now() > deadline) {
+                return Err(Outcome::OutOfTime);
+            }
+            let orientations = &shapes[*shape_index].orientations;
+            for (orientation_index, orientation) in orientations.iter().enumerate() {
+                for r in 0..self.height {
+                    for c in 0..self.width {
+                        if let Some(cells) = self.cells_covered(orientation, r, c) {
+                            let mut columns = vec![column];
+                            columns.extend(cells.iter().map(|cell| wanted.len() + cell));
+                            dlx.add_row(&columns);
+                            rows.push(Placement {
+                                shape: *shape_index,
+                                orientation: orientation_index,
+                                row: r,
+                                col: c,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        match dlx.solve_before(deadline) {
+            Outcome::Solved(chosen) => Ok(chosen.into_iter().map(|row| rows[row]).collect()),
+            outcome => Err(outcome),
+        }
+    }
+
+    /// The (row-major) cells an orientation would cover with its top-left at
+    /// `r`,`c`, or `None` if it would hang off the edge
+    fn cells_covered(&self, orientation: &[Vec<bool>], r: usize, c: usize) -> Option<Vec<usize>> {
+        let mut cells = vec![];
+        for (orientation_r, row) in orientation.iter().enumerate() {
+            for (orientation_c, filled) in row.iter().enumerate() {
+                if *filled {
+                    let (cell_r, cell_c) = (r + orientation_r, c + orientation_c);
+                    if cell_r >= self.height || cell_c >= self.width {
+                        return None;
+                    }
+                    cells.push(cell_r * self.width + cell_c);
+                }
+            }
+        }
+        Some(cells)
+    }
+
+    // The board is a bitmask per row, like `Mask`, so checking whether a shape
+    // fits is an AND per row and placing it is an OR. Placing works on the one
+    // board in place, and gets undone (with XOR) on the way back out. Once
+    // every present is placed, each level adds its own placement on the way
+    // back up.
+    fn try_shapes(
+        &self,
+        board: &mut [u64],
+        shapes: &[Shape],
+        r: usize,
+        c: usize,
+        placed: &mut [usize],
+        deadline: &Deadline,
+    ) -> Option<Vec<Placement>> {
+        if deadline.passed() {
+            return None;
+        }
+        // println!("{r},{c}");
+        // println!("{placed:?}");
+        for shape_index in 0..shapes.len() {
+            if placed[shape_index] < self.targets[shape_index]
+                && let Some(placements) =
+                    self.try_orientations(board, shapes, shape_index, (r, c), placed, deadline)
+            {
+                return Some(placements);
+            }
+        }
+
+        None
+    }
+
+    fn try_orientations(
+        &self,
+        board: &mut [u64],
+        shapes: &[Shape],
+        shape_index: usize,
+        (r, c): (usize, usize),
+        placed: &mut [usize],
+        deadline: &Deadline,
+    ) -> Option<Vec<Placement>> {
+        for (orientation, mask) in shapes[shape_index].masks.iter().enumerate() {
+            // line the mask up so its first filled cell lands on (r, c), since
+            // that's the first empty cell it has to cover
+            let offset = mask.rows[0].trailing_zeros() as usize;
+            if c < offset || r + mask.height > self.height || c - offset + mask.width > self.width {
+                continue;
+            }
+            let left = c - offset;
+            let rows = &mask.rows[..mask.height];
+            if rows
+                .iter()
+                .enumerate()
+                .any(|(mask_r, row)| board[r + mask_r] & (row << left) != 0)
+            {
+                continue;
+            }
+
+            // if we got here, it fit!
+            for (mask_r, row) in rows.iter().enumerate() {
+                board[r + mask_r] |= row << left;
+            }
+            placed[shape_index] += 1;
+
+            let rest = if placed == self.targets.as_slice() {
+                Some(vec![])
+            } else {
+                self.try_next_location(board, shapes, r, c, placed, deadline)
+            };
+
+            placed[shape_index] -= 1;
+            for (mask_r, row) in rows.iter().enumerate() {
+                board[r + mask_r] ^= row << left;
+            }
+
+            if let Some(mut placements) = rest {
+                placements.push(Placement {
+                    shape: shape_index,
+                    orientation,
+                    row: r,
+                    col: left,
+                });
+                return Some(placements);
+            }
+        }
+
+        None
+    }
+
+    fn try_next_location(
+        &self,
+        board: &mut [u64],
+        shapes: &[Shape],
+        mut r: usize,
+        mut c: usize,
+        placed: &mut [usize],
+        deadline: &Deadline,
+    ) -> Option<Vec<Placement>> {
+        let needed: usize = shapes
+            .iter()
+            .zip(self.targets.iter())
+            .map(|(shape, count)| shape.area * count)
+            .sum();
+        let spare = self.width * self.height - needed;
+        loop {
+            c += 1;
+            if c == self.width {
+                c = 0;
+                r += 1;
+                if r == self.height {
+                    return None;
+                }
+            }
+
+            if board[r] & (1 << c) == 0 {
+                // there's only so much room to leave empty
+                if self.left_empty(board, r, c) > spare {
+                    return None;
+                }
+                if let Some(placements) = self.try_shapes(board, shapes, r, c, placed, deadline) {
+                    return Some(placements);
+                }
+            }
+        }
+    }
+
+    /// How many of the cells before `r`,`c` (reading across, then down) are
+    /// empty. Every present placed from here on has its first cell at `r`,`c`
+    /// or after it, so none of them can fill these.
+    fn left_empty(&self, board: &[u64], r: usize, c: usize) -> usize {
+        let filled = board[..r].iter().map(|row| row.count_ones()).sum::<u32>()
+            + (board[r] & ((1 << c) - 1)).count_ones();
+        r * self.width + c - filled as usize
+    }
+}
+
+impl Region {
+    /// Which present (by index into `placements`) covers each cell
+    fn cover(&self, shapes: &[Shape], placements: &[Placement]) -> Vec<Vec<Option<usize>>> {
+        let mut grid = vec![vec![None; self.width]; self.height];
+        for (present, placement) in placements.iter().enumerate() {
+            let orientation = &shapes[placement.shape].orientations[placement.orientation];
+            for (r, row) in orientation.iter().enumerate() {
+                for (c, filled) in row.iter().enumerate() {
+                    if *filled {
+                        grid[placement.row + r][placement.col + c] = Some(present);
+                    }
+                }
+            }
+        }
+        grid
+    }
+
+    /// The region with each present drawn as its own letter (and, optionally,
+    /// in its shape's colour), and empty cells as dots
+    fn render(&self, shapes: &[Shape], placements: &[Placement], colour: bool) -> String {
+        let mut out = String::new();
+        for row in self.cover(shapes, placements) {
+            for cell in row {
+                match cell {
+                    None => out.push('.'),
+                    Some(present) if colour => {
+                        let code = ANSI_COLOURS[placements[present].shape % ANSI_COLOURS.len()];
+                        out.push_str(&format!("\x1b[{code}m{}\x1b[0m", letter(present)));
+                    }
+                    Some(present) => out.push(letter(present)),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Foreground colours for the shapes, in order
+const ANSI_COLOURS: [&str; 12] = [
+    "31", "32", "33", "34", "35", "36", "91", "92", "93", "94", "95", "96",
+];
+const SVG_COLOURS: [&str; 12] = [
+    "tomato",
+    "mediumseagreen",
+    "gold",
+    "royalblue",
+    "orchid",
+    "darkturquoise",
+    "salmon",
+    "yellowgreen",
+    "orange",
+    "cornflowerblue",
+    "plum",
+    "aquamarine",
+];
+const SVG_CELL: usize = 20;
+
+fn letter(present: usize) -> char {
+    const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    LETTERS[present % LETTERS.len()] as char
+}
+
+/// Each packed region one below the other, with its presents coloured by
+/// shape and lettered like the text version
+fn to_svg(shapes: &[Shape], packed: &[(&Region, &[Placement])]) -> String {
+    let width = packed
+        .iter()
+        .map(|(region, _)| region.width)
+        .max()
+        .unwrap_or(0)
+        * SVG_CELL;
+    let height: usize = packed
+        .iter()
+        .map(|(region, _)| (region.height + 2) * SVG_CELL)
+        .sum();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"monospace\" font-size=\"{}\">\n",
+        SVG_CELL * 3 / 4
+    );
+    let mut top = 0;
+    for (region, placements) in packed {
+        svg.push_str(&format!(
+            "<text x=\"0\" y=\"{}\">{}x{}: {:?}</text>\n",
+            top + SVG_CELL * 3 / 4,
+            region.width,
+            region.height,
+            region.targets
+        ));
+        top += SVG_CELL;
+        for (r, row) in region.cover(shapes, placements).iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                let (x, y) = (c * SVG_CELL, top + r * SVG_CELL);
+                let fill = match cell {
+                    Some(present) => SVG_COLOURS[placements[*present].shape % SVG_COLOURS.len()],
+                    None => "white",
+                };
+                svg.push_str(&format!(
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" fill=\"{fill}\" stroke=\"lightgrey\"/>\n"
+                ));
+                if let Some(present) = cell {
+                    svg.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                        x + SVG_CELL / 2,
+                        y + SVG_CELL * 3 / 4,
+                        letter(*present)
+                    ));
+                }
+            }
+        }
+        top += (region.height + 1) * SVG_CELL;
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+struct RegionResult {
+    verdict: Verdict,
+    elapsed: Duration,
+}
+
+/// Running totals for the progress line
+#[derive(Default)]
+struct Progress {
+    done: usize,
+    fits: usize,
+    does_not_fit: usize,
+    unknown: usize,
+    /// The index of the slowest region so far, and how long it took
+    slowest: Option<(usize, Duration)>,
+}
+
+impl Progress {
+    fn record(&mut self, index: usize, result: &RegionResult) {
+        self.done += 1;
+        match result.verdict {
+            Verdict::Fits(..) => self.fits += 1,
+            Verdict::DoesNotFit(_) => self.does_not_fit += 1,
+            Verdict::Unknown => self.unknown += 1,
+        }
+        if self
+            .slowest
+            .is_none_or(|(_, elapsed)| result.elapsed > elapsed)
+        {
+            self.slowest = Some((index, result.elapsed));
+        }
+    }
+
+    fn line(&self, total: usize) -> String {
+        let mut line = format!(
+            "{}/{total} done: {} yes, {} no, {} out of time",
+            self.done, self.fits, self.does_not_fit, self.unknown
+        );
+        if let Some((index, elapsed)) = self.slowest {
+            line.push_str(&format!(", slowest #{index} ({elapsed:?})"));
+        }
+        line
+    }
+}
+
+/// Hands the regions out to `jobs` threads, and keeps a progress line up to
+/// date on stderr (if it's a terminal) as they finish. The results come back
+/// in the same order as the regions.
+fn solve_all(
+    regions: &[Region],
+    shapes: &[Shape],
+    solver: Solver,
+    budget: Option<Duration>,
+    jobs: usize,
+) -> Vec<RegionResult> {
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<RegionResult>>> =
+        regions.iter().map(|_| Mutex::new(None)).collect();
+    let progress = Mutex::new(Progress::default());
+    let live = io::stderr().is_terminal();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, regions.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let r = next.fetch_add(1, Ordering::Relaxed);
+                    if r >= regions.len() {
+                        break;
+                    }
+                    let start = Instant::now();
+                    let verdict = regions[r].can_fit(shapes, solver, budget);
+                    let result = RegionResult {
+                        verdict,
+                        elapsed: start.elapsed(),
+                    };
+
+                    let mut progress = progress.lock().unwrap();
+                    progress.record(r, &result);
+                    if live {
+                        eprint!("\r\x1b[K{}", progress.line(regions.len()));
+                    }
+                    *results[r].lock().unwrap() = Some(result);
+                }
+            });
+        }
+    });
+    if live && !regions.is_empty() {
+        eprintln!();
+    }
+
+    results
+        .into_iter()
+        .map(|r| r.into_inner().unwrap().unwrap())
+        .collect()
+}
+
+/// What happened with one region, for JSON output
+#[derive(Serialize)]
+struct RegionDetail<'a> {
+    width: usize,
+    height: usize,
+    targets: &'a [usize],
+    /// `fits`, `does not fit`, or `unknown` if it ran out of time
+    verdict: &'static str,
+    /// Which check settled it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'static str>,
+    seconds: f64,
+    /// Where each present went, with `--show`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    placements: Option<&'a [Placement]>,
+}
+
+fn process(shapes: Vec<Shape>, regions: Vec<Region>, args: &Args, report: &mut Report) {
+    let budget = (args.budget_ms > 0).then(|| Duration::from_millis(args.budget_ms));
+    let colour = io::stdout().is_terminal();
+
+    let jobs = args
+        .jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let start = Instant::now();
+    let results = solve_all(&regions, &shapes, args.solver, budget, jobs);
+    let elapsed = start.elapsed();
+
+    let mut unknown = vec![];
+    let mut packed = vec![];
+    for (region, result) in regions.iter().zip(results) {
+        report.say(format!(
+            "{region:?}: {} in {:?}",
+            result.verdict, result.elapsed
+        ));
+        let (verdict, reason) = match &result.verdict {
+            Verdict::Fits(why, _) => ("fits", Some(*why)),
+            Verdict::DoesNotFit(why) => ("does not fit", Some(*why)),
+            Verdict::Unknown => ("unknown", None),
+        };
+        let placements = match &result.verdict {
+            Verdict::Fits(_, placements) if args.show => Some(placements.as_slice()),
+            _ => None,
+        };
+        report.detail(RegionDetail {
+            width: region.width,
+            height: region.height,
+            targets: &region.targets,
+            verdict,
+            reason,
+            seconds: result.elapsed.as_secs_f64(),
+            placements,
+        });
+
+        match result.verdict {
+            Verdict::Fits(_, placements) => {
+                if args.show && report.is_text() {
+                    print!("{}", region.render(&shapes, &placements, colour));
+                }
+                packed.push((region, placements));
+            }
+            Verdict::DoesNotFit(_) => {}
+            Verdict::Unknown => unknown.push(region),
+        }
+    }
+
+    if let Some(path) = &args.svg {
+        let packed: Vec<(&Region, &[Placement])> = packed
+            .iter()
+            .map(|(region, placements)| (*region, placements.as_slice()))
+            .collect();
+        if let Err(e) = fs::write(path, to_svg(&shapes, &packed)) {
+            report.fail(format!("Failed to write {}: {e}", path.display()));
+        }
+    }
+
+    if !unknown.is_empty() {
+        report.say(format!("{} regions ran out of time:", unknown.len()));
+        for region in unknown {
+            report.say(format!("  {region:?}"));
+        }
+    }
+    report.answer(1, packed.len(), elapsed);
+}
+
+fn rotate(shape: Vec<Vec<bool>>) -> Vec<Vec<bool>> {
+    let (height, width) = (shape.len(), shape.first().map_or(0, |row| row.len()));
+    let mut rotated = vec![vec![false; height]; width];
+
+    for r in 0..height {
+        for c in 0..width {
+            rotated[width - 1 - c][r] = shape[r][c];
+        }
+    }
+
+    rotated
+}
+
+fn flip(shape: Vec<Vec<bool>>) -> Vec<Vec<bool>> {
+    let width = shape.first().map_or(0, |row| row.len());
+    let mut flipped = vec![vec![false; width]; shape.len()];
+
+    for r in 0..shape.len() {
+        for c in 0..width {
+            flipped[r][width - 1 - c] = shape[r][c];
+        }
+    }
+
+    flipped
+}
+
+/// Trims off any empty rows and columns around the edges
+fn normalize(shape: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let filled_rows: Vec<usize> = (0..shape.len())
+        .filter(|r| shape[*r].iter().any(|cell| *cell))
+        .collect();
+    let width = shape.first().map_or(0, |row| row.len());
+    let filled_cols: Vec<usize> = (0..width)
+        .filter(|c| shape.iter().any(|row| row[*c]))
+        .collect();
+    let (Some(top), Some(bottom)) = (filled_rows.first(), filled_rows.last()) else {
+        return vec![];
+    };
+    let (left, right) = (filled_cols[0], filled_cols[filled_cols.len() - 1]);
+
+    shape[*top..=*bottom]
+        .iter()
+        .map(|row| row[left..=right].to_vec())
+        .collect()
+}
+
+/// Region lines look like `12x5: 1 0 1 0 2 2`; present headers are just `4:`
+fn is_region(line: &str) -> bool {
+    line.split_once(":")
+        .is_some_and(|(size, _)| size.contains("x"))
+}
+
+/// The presents first, numbered from 0 in order, then one region per line
+pub fn parse(data: &str) -> Result<(Vec<Shape>, Vec<Region>), ParseError> {
+    let mut lines = data.lines().enumerate().peekable();
+    let mut shapes: Vec<Shape> = vec![];
+    while let Some((i, line)) = lines.peek().copied() {
+        if is_region(line) {
+            break;
+        }
+        lines.next();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(header) = line.trim().strip_suffix(":") else {
+            return Err(ParseError::new(i + 1, ErrorKind::Missing("':'")));
+        };
+        let header: usize = number(i + 1, header)?;
+        if header != shapes.len() {
+            let why = format!("present {header} should be numbered {}", shapes.len());
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        shapes.push(parse_present(i + 1, &mut lines)?);
+    }
+
+    let mut regions = vec![];
+    for (i, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+        let region = parse_region(i + 1, line)?;
+        if region.targets.len() != shapes.len() {
+            let why = format!(
+                "{} counts, but there are {} presents",
+                region.targets.len(),
+                shapes.len()
+            );
+            return Err(ParseError::new(i + 1, ErrorKind::Invalid(why)));
+        }
+        regions.push(region);
+    }
+
+    Ok((shapes, regions))
+}
+
+/// The most rows or columns a present can have, so each row of any
+/// orientation fits in a `Mask`'s u64
+const MAX_PRESENT_SIZE: usize = u64::BITS as usize;
+
+/// Reads the rows of `#` and `.` after the header on line `header`, up to a
+/// blank line or the first region, of any size. Short rows are padded out
+/// with empty cells.
+fn parse_present<'a>(
+    header: usize,
+    lines: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>,
+) -> Result<Shape, ParseError> {
+    let mut bools: Vec<Vec<bool>> = vec![];
+    while let Some((i, row)) = lines.next_if(|(_, row)| !row.trim().is_empty() && !is_region(row)) {
+        let row = row
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(ParseError::new(i + 1, ErrorKind::UnexpectedChar(c))),
+            })
+            .collect::<Result<Vec<bool>, ParseError>>()?;
+        bools.push(row);
+    }
+    let width = bools.iter().map(|row| row.len()).max().unwrap_or(0);
+    if width > MAX_PRESENT_SIZE || bools.len() > MAX_PRESENT_SIZE {
+        let why = format!("presents can be at most {MAX_PRESENT_SIZE} cells across");
+        return Err(ParseError::new(header, ErrorKind::Invalid(why)));
+    }
+    if !bools.iter().flatten().any(|cell| *cell) {
+        return Err(ParseError::new(header, ErrorKind::Missing("present")));
+    }
+    for row in bools.iter_mut() {
+        row.resize(width, false);
+    }
+
+    Ok(Shape::new(&bools))
+}
+
+/// The most cells a region can have. Even the cheap checks take time and
+/// memory in proportion to the cells, so this keeps them quick.
+const MAX_REGION_CELLS: usize = 1 << 20;
+
+fn parse_region(line_no: usize, line: &str) -> Result<Region, ParseError> {
+    let mut vals = line.split_whitespace();
+
+    let Some((width, height)) = vals
+        .next()
+        .and_then(|size| size.strip_suffix(":"))
+        .and_then(|size| size.split_once("x"))
+    else {
+        return Err(ParseError::new(line_no, ErrorKind::Missing("region size")));
+    };
+
+    let (width, height): (usize, usize) = (number(line_no, width)?, number(line_no, height)?);
+    if width
+        .checked_mul(height)
+        .is_none_or(|cells| cells > MAX_REGION_CELLS)
+    {
+        let why = format!("a {width}x{height} region has more than {MAX_REGION_CELLS} cells");
+        return Err(ParseError::new(line_no, ErrorKind::Invalid(why)));
+    }
+
+    Ok(Region {
+        width,
+        height,
+        targets: vals
+            .map(|val| number(line_no, val))
+            .collect::<Result<Vec<usize>, ParseError>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::generate;
+    use aoc::rng::Rng;
+    use proptest::prelude::*;
+
+    fn str_to_bools(row1: &str, row2: &str, row3: &str) -> Vec<Vec<bool>> {
+        rows_to_bools(&[row1, row2, row3])
+    }
+
+    fn rows_to_bools(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_rotate() {
+        let original = str_to_bools(
+            "##.",
+            "...",
+            "..#",
+        );
+
+        let expected = str_to_bools(
+            "..#",
+            "#..",
+            "#..",
+        );
+
+        assert_eq!(expected, rotate(original), "rotation works");
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_flip() {
+        let original = str_to_bools(
+            "##.",
+            "...",
+            "..#",
+        );
+
+        let expected = str_to_bools(
+            ".##",
+            "...",
+            "#..",
+        );
+
+        assert_eq!(expected, flip(original), "rotation works");
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_orientations() {
+        let original = str_to_bools(
+            "##.",
+            "...",
+            "..#",
+        );
+        let expected_orientations = vec![
+            str_to_bools(
+                "##.",
+                "...",
+                "..#",
+            ),
+            str_to_bools(
+                ".##",
+                "...",
+                "#..",
+            ),
+
+            str_to_bools(
+                "..#",
+                "..#",
+                "#..",
+            ),
+            str_to_bools(
+                "#..",
+                "..#",
+                "..#",
+            ),
+
+            str_to_bools(
+                "#..",
+                "...",
+                ".##",
+            ),
+            str_to_bools(
+                "..#",
+                "...",
+                "##.",
+            ),
+
+            str_to_bools(
+                "..#",
+                "#..",
+                "#..",
+            ),
+            str_to_bools(
+                "#..",
+                "#..",
+                "..#",
+            ),
+        ];
+        check(expected_orientations, Shape::orientations(&original), "all possible");
+
+
+        let original = str_to_bools(
+            ".#.",
+            "..#",
+            "...",
+        );
+        // trimmed down to the filled cells, the four corners of the 3x3 are the same
+        let expected_orientations = vec![
+            rows_to_bools(&[
+                "#.",
+                ".#",
+            ]),
+            rows_to_bools(&[
+                ".#",
+                "#.",
+            ]),
+        ];
+        check(expected_orientations, Shape::orientations(&original), "flips match rotations");
+
+        let original = str_to_bools(
+            "#..",
+            ".#.",
+            "..#",
+        );
+        let expected_orientations = vec![
+            str_to_bools(
+                "#..",
+                ".#.",
+                "..#",
+            ),
+            str_to_bools(
+                "..#",
+                ".#.",
+                "#..",
+            ),
+        ];
+        check(expected_orientations, Shape::orientations(&original), "only two");
+
+        let original = rows_to_bools(&[
+            "####",
+            "#...",
+        ]);
+        let expected_orientations = vec![
+            rows_to_bools(&["####", "#..."]),
+            rows_to_bools(&["####", "...#"]),
+            rows_to_bools(&["#...", "####"]),
+            rows_to_bools(&["...#", "####"]),
+            rows_to_bools(&["##", "#.", "#.", "#."]),
+            rows_to_bools(&["##", ".#", ".#", ".#"]),
+            rows_to_bools(&["#.", "#.", "#.", "##"]),
+            rows_to_bools(&[".#", ".#", ".#", "##"]),
+        ];
+        check(expected_orientations, Shape::orientations(&original), "non-square");
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_normalize() {
+        let original = str_to_bools(
+            "...",
+            ".#.",
+            ".##",
+        );
+        assert_eq!(rows_to_bools(&["#.", "##"]), normalize(&original), "trims to the corner");
+    }
+
+    #[test]
+    fn test_parse_any_size() {
+        let (shapes, regions) = parse("0:\n####\n#\n\n1:\n#\n\n5x2: 1 2\n").unwrap();
+        assert_eq!(5, shapes[0].area, "short rows are padded");
+        assert_eq!(8, shapes[0].orientations.len());
+        assert_eq!(
+            vec![vec![vec![true]]],
+            shapes[1].orientations,
+            "single cell"
+        );
+        assert_eq!(vec![1, 2], regions[0].targets);
+
+        let (shapes, regions) = parse("0:\n##\n3x1: 1\n").unwrap();
+        assert_eq!(2, shapes[0].area, "no blank line before the regions");
+        assert_eq!(1, regions.len());
+    }
+
+    #[test]
+    fn test_bad_input() {
+        let error = |input: &str| parse(input).unwrap_err();
+        assert_eq!(
+            ParseError::new(2, ErrorKind::UnexpectedChar('x')),
+            error("0:\n#x")
+        );
+        assert_eq!(
+            ParseError::new(1, ErrorKind::Missing("present")),
+            error("0:\n...\n")
+        );
+        assert!(
+            matches!(error("1:\n#\n").kind, ErrorKind::Invalid(_)),
+            "numbered from 0"
+        );
+        assert!(
+            matches!(error("0:\n#\n\n2x2: 1 1").kind, ErrorKind::Invalid(_)),
+            "too many counts"
+        );
+        assert!(
+            matches!(
+                error(&format!("0:\n{}", "#".repeat(65))).kind,
+                ErrorKind::Invalid(_)
+            ),
+            "too wide"
+        );
+        assert!(matches!(
+            error("0:\n#\n\n2xx: 1").kind,
+            ErrorKind::BadNumber(_)
+        ));
+        assert!(
+            matches!(
+                error("0:\n#\n\n99999999999x99999999999: 0").kind,
+                ErrorKind::Invalid(_)
+            ),
+            "too many cells"
+        );
+        assert!(parse("0:\n#\n\n1024x1024: 0").is_ok());
+        assert!(
+            matches!(error("0:\n#\n\n1025x1024: 0").kind, ErrorKind::Invalid(_)),
+            "too big a region"
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_pre_checks() {
+        let t = Shape::new(&str_to_bools(
+            "###",
+            ".#.",
+            "...",
+        ));
+        let shapes = [t];
+        let region = |width, height, count| Region { width, height, targets: vec![count] };
+
+        assert!(matches!(region(6, 7, 4).can_fit(&shapes, Solver::Dlx, None), Verdict::Fits("one present per block", _)));
+        assert_eq!(Verdict::DoesNotFit("not enough area"), region(4, 3, 4).can_fit(&shapes, Solver::Dlx, None));
+        assert_eq!(Verdict::DoesNotFit("not enough area"), region(4, 3, usize::MAX).can_fit(&shapes, Solver::Dlx, None));
+        // each T covers 3 of one colour and 1 of the other, so an odd number
+        // of them can't cover a board with as many of each
+        assert_eq!(Verdict::DoesNotFit("checkerboard colouring"), region(4, 3, 3).can_fit(&shapes, Solver::Dlx, None));
+        assert!(matches!(region(4, 4, 4).can_fit(&shapes, Solver::Backtrack, None), Verdict::Fits("search", _)));
+        // as many presents as the biggest region has room for; each shape is
+        // only a pass over the possible totals, however many of it there are
+        assert!(region(1024, 1024, 1 << 18).colours_balance(&shapes));
+        assert!(region(1023, 1024, (1023 << 8) - 1).colours_balance(&shapes));
+    }
+
+    #[test]
+    fn test_backtrack() {
+        let (shapes, regions) = parse("0:\n.##\n###\n#..\n\n6x4: 3\n").unwrap();
+        assert!(
+            matches!(
+                regions[0].can_fit(&shapes, Solver::Backtrack, None),
+                Verdict::Fits("search", _)
+            ),
+            "only fits with the top-left cell empty"
+        );
+
+        let (shapes, regions) = parse("0:\n##\n\n70x2: 40\n").unwrap();
+        assert!(
+            matches!(
+                regions[0].can_fit(&shapes, Solver::Backtrack, None),
+                Verdict::Fits("search", _)
+            ),
+            "too wide for a u64 per row"
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_render() {
+        let t = Shape::new(&str_to_bools(
+            "###",
+            ".#.",
+            "...",
+        ));
+        let shapes = [t];
+        let region = Region { width: 4, height: 4, targets: vec![4] };
+
+        for solver in [Solver::Dlx, Solver::Backtrack] {
+            let Verdict::Fits(_, placements) = region.can_fit(&shapes, solver, None) else {
+                panic!("four Ts tile a 4x4 square");
+            };
+            let drawn = region.render(&shapes, &placements, false);
+            assert_eq!(4, drawn.lines().count(), "one line per row");
+            for present in 0..4 {
+                let count = drawn.chars().filter(|c| *c == letter(present)).count();
+                assert_eq!(4, count, "each present covers 4 cells");
+            }
+        }
+
+        let flat = rows_to_bools(&["###", ".#."]);
+        let orientation = shapes[0].orientations.iter().position(|o| *o == flat).unwrap();
+        let placements = [Placement { shape: 0, orientation, row: 1, col: 0 }];
+        let region = Region { width: 3, height: 3, targets: vec![1] };
+        let expected = "...\nAAA\n.A.\n";
+        assert_eq!(expected, region.render(&shapes, &placements, false));
+    }
+
+    /// Whether the placements put exactly the right presents in the region,
+    /// without any overlapping or hanging off the edge
+    fn is_packing(region: &Region, shapes: &[Shape], placements: &[Placement]) -> bool {
+        let mut counts = vec![0; shapes.len()];
+        let mut covered = vec![vec![false; region.width]; region.height];
+        for placement in placements {
+            counts[placement.shape] += 1;
+            let orientation = &shapes[placement.shape].orientations[placement.orientation];
+            for (r, row) in orientation.iter().enumerate() {
+                for (c, filled) in row.iter().enumerate() {
+                    let (r, c) = (placement.row + r, placement.col + c);
+                    if *filled {
+                        if r >= region.height || c >= region.width || covered[r][c] {
+                            return false;
+                        }
+                        covered[r][c] = true;
+                    }
+                }
+            }
+        }
+        counts == region.targets
+    }
+
+    /// Presents and regions like `aoc gen 12` makes, but small enough for
+    /// either solver to settle quickly
+    fn packings() -> impl Strategy<Value = String> {
+        any::<u64>().prop_map(|seed| {
+            let mut rng = Rng::new(seed);
+            let (mut input, areas) = generate::presents(&mut rng);
+            for _ in 0..4 {
+                input.push_str(&generate::region(&mut rng, &areas, 3..7));
+            }
+            input
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn solvers_agree(input in packings()) {
+            let (shapes, regions) = parse(&input).unwrap();
+            for region in &regions {
+                let dlx = region.can_fit(&shapes, Solver::Dlx, None);
+                let backtrack = region.can_fit(&shapes, Solver::Backtrack, None);
+                for verdict in [&dlx, &backtrack] {
+                    if let Verdict::Fits(_, placements) = verdict {
+                        prop_assert!(is_packing(region, &shapes, placements), "{:?}: {}", region, verdict);
+                    }
+                }
+                prop_assert_eq!(
+                    matches!(dlx, Verdict::Fits(..)),
+                    matches!(backtrack, Verdict::Fits(..)),
+                    "{:?}: dlx says {}, backtrack says {}", region, dlx, backtrack
+                );
+            }
+        }
+    }
+
+    fn check(expecteds: Vec<Vec<Vec<bool>>>, actuals: Vec<Vec<Vec<bool>>>, desc: &str) {
+        let mut match_count = 0;
+        'expected: for expected in &expecteds {
+            for actual in &actuals {
+                if *expected == *actual {
+                    match_count += 1;
+                    continue 'expected;
+                }
+            }
+        }
+        assert_eq!(
+            expecteds.len(),
+            match_count,
+            "Got {desc} orientations; here are the actuals: {actuals:#?}"
+        );
+        assert_eq!(
+            expecteds.len(),
+            actuals.len(),
+            "Got extra {desc} orientations; here are the actuals: {actuals:#?}"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use crate::input;
+use crate::report::{Format, Report, Run};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Everything a day's `run` does apart from the solving: expands `inputs`,
+/// reads and parses each one in turn and hands it to `process`, then writes
+/// out what they all came to. `single` is the name of an option that only
+/// works with one input, if one was given.
+pub fn run<T, E: fmt::Display>(
+    day: u8,
+    inputs: &[PathBuf],
+    format: Format,
+    single: Option<&str>,
+    parse: impl Fn(&str) -> Result<T, E>,
+    mut process: impl FnMut(T, &mut Report),
+) {
+    let mut run = Run::new(day, format);
+
+    let inputs = match input::expand(inputs) {
+        Ok(inputs) => inputs,
+        Err(e) => run.abort(e),
+    };
+
+    if inputs.len() > 1
+        && let Some(option) = single
+    {
+        run.abort(format!("{option} only works with a single input"));
+    }
+
+    for input in &inputs {
+        let report = run.start(input, inputs.len() > 1);
+
+        let contents = match input.read() {
+            Ok(s) => s,
+            Err(e) => {
+                report.fail(format!("Failed to read {input}: {e}"));
+                continue;
+            }
+        };
+
+        let parsed = match parse(&contents) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                report.fail(format!("Failed to parse {input}: {e}"));
+                continue;
+            }
+        };
+
+        process(parsed, report);
+    }
+    run.finish();
+}
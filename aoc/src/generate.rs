@@ -0,0 +1,340 @@
+// Each generator builds its input out of something that's valid by
+// construction, rather than generating freely and then checking.
+
+use crate::rng::Rng;
+use std::ops::Range;
+
+/// Red tiles going round a polygon made of columns side by side, each one
+/// overlapping the one before it (and, half the time, turned on its side).
+/// At scale 1 that's a few hundred corners on a floor about 100,000 across.
+pub fn day09(rng: &mut Rng, scale: usize) -> String {
+    polygon(rng, 125 * scale, 1500, 100_000)
+        .into_iter()
+        .map(|(x, y)| format!("{x},{y}\n"))
+        .collect()
+}
+
+/// The corners of a polygon of `columns` columns, each less than `width` wide
+/// and fitting between 0 and `height`, in order round the outside
+pub fn polygon(rng: &mut Rng, columns: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+    let wander = (height * 2 / 25).max(1);
+    let mut xs = vec![rng.range(1..width)];
+    let mut spans: Vec<(usize, usize)> = vec![];
+    let (mut top, mut bottom) = (height * 2 / 5, height * 3 / 5);
+    for _ in 0..columns {
+        xs.push(xs.last().unwrap() + rng.range(1..width));
+
+        // wander up and down, but always overlap the previous column, so the
+        // edges between them can't touch
+        top = (top + rng.range(0..wander * 2))
+            .saturating_sub(wander)
+            .clamp(1, height * 49 / 50);
+        bottom = (bottom + rng.range(0..wander * 2))
+            .saturating_sub(wander)
+            .clamp(top + 1, height);
+        if let Some((prev_top, prev_bottom)) = spans.last() {
+            top = top.min(prev_bottom - 1);
+            bottom = bottom.max(prev_top + 1);
+        }
+        spans.push((top, bottom));
+    }
+
+    let mut corners = vec![];
+    for (i, (top, _)) in spans.iter().enumerate() {
+        corners.push((xs[i], *top));
+        corners.push((xs[i + 1], *top));
+    }
+    for (i, (_, bottom)) in spans.iter().enumerate().rev() {
+        corners.push((xs[i + 1], *bottom));
+        corners.push((xs[i], *bottom));
+    }
+    let corners = remove_straight_runs(corners);
+    if rng.coin() {
+        corners.into_iter().map(|(x, y)| (y, x)).collect()
+    } else {
+        corners
+    }
+}
+
+/// Drops repeated points, and points in the middle of a straight line, so that
+/// every point left is a corner
+fn remove_straight_runs(mut points: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    let n = points.len();
+    (0..n)
+        .filter(|i| {
+            let (prev, point, next) = (points[(i + n - 1) % n], points[*i], points[(i + 1) % n]);
+            let straight = (prev.0 == point.0 && point.0 == next.0)
+                || (prev.1 == point.1 && point.1 == next.1);
+            !straight
+        })
+        .map(|i| points[i])
+        .collect()
+}
+
+/// Machines whose joltage targets (and light patterns) come from actually
+/// pressing their buttons, so they always have a solution
+pub fn day10(rng: &mut Rng, scale: usize) -> String {
+    (0..180 * scale).map(|_| machine(rng, 3..11, 25)).collect()
+}
+
+/// One machine's line, with a light (and joltage) count in `lights` and each
+/// button pressed fewer than `presses` times
+pub fn machine(rng: &mut Rng, lights: Range<usize>, presses: usize) -> String {
+    let light_count = rng.range(lights);
+    let button_count = rng.range(light_count.saturating_sub(1).max(2)..light_count + 4);
+
+    let mut buttons: Vec<Vec<usize>> = vec![];
+    for _ in 0..button_count {
+        let mut wired: Vec<usize> = vec![];
+        for light in 0..light_count {
+            if rng.range(0..3) == 0 {
+                wired.push(light);
+            }
+        }
+        if wired.is_empty() {
+            wired.push(rng.range(0..light_count));
+        }
+        buttons.push(wired);
+    }
+
+    let mut lights = vec![false; light_count];
+    let mut joltages = vec![0; light_count];
+    for button in &buttons {
+        let presses = rng.range(0..presses);
+        for light in button {
+            joltages[*light] += presses;
+            lights[*light] ^= presses % 2 == 1;
+        }
+    }
+
+    let lights: String = lights
+        .iter()
+        .map(|on| if *on { '#' } else { '.' })
+        .collect();
+    let buttons: Vec<String> = buttons.iter().map(|b| format!("({})", join(b))).collect();
+    format!("[{lights}] {} {{{}}}\n", buttons.join(" "), join(&joltages))
+}
+
+fn join(values: &[usize]) -> String {
+    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    values.join(",")
+}
+
+/// Devices in layers, each only feeding the next layer (or now and then the
+/// one after), from `svr` down to `out`, with `you`, `dac` and `fft` along the
+/// way. Every device has at least one input and one output, so every path
+/// that starts at `svr` ends at `out`; and since most devices have two or
+/// three outputs, the number of paths grows exponentially with the scale.
+pub fn day11(rng: &mut Rng, scale: usize) -> String {
+    let layer_count = 12 * scale;
+    let reserved = ["svr", "you", "dac", "fft", "out"];
+    let mut names = (0..)
+        .map(device_name)
+        .filter(|name| !reserved.contains(&name.as_str()));
+    let mut layers: Vec<Vec<String>> = vec![vec!["svr".to_string()]];
+    for _ in 1..layer_count {
+        let layer = (0..rng.range(20..60))
+            .map(|_| names.next().unwrap())
+            .collect();
+        layers.push(layer);
+    }
+    layers[1].push("you".to_string());
+    layers[layer_count / 3].push("dac".to_string());
+    layers[layer_count * 2 / 3].push("fft".to_string());
+
+    let mut lines: Vec<String> = vec![];
+    for k in 0..layers.len() {
+        let Some(next) = layers.get(k + 1) else {
+            for device in &layers[k] {
+                lines.push(format!("{device}: out"));
+            }
+            break;
+        };
+
+        let mut outputs: Vec<Vec<&String>> = vec![vec![]; layers[k].len()];
+        for device in next {
+            outputs[rng.range(0..layers[k].len())].push(device);
+        }
+        for device_outputs in outputs.iter_mut() {
+            let wanted = rng.range(1..4);
+            while device_outputs.len() < wanted {
+                let candidates = match layers.get(k + 2) {
+                    Some(after) if rng.range(0..8) == 0 => after,
+                    _ => next,
+                };
+                let output = rng.pick(candidates);
+                if !device_outputs.contains(&output) {
+                    device_outputs.push(output);
+                }
+            }
+        }
+
+        for (device, device_outputs) in layers[k].iter().zip(outputs) {
+            let names: Vec<&str> = device_outputs.iter().map(|o| o.as_str()).collect();
+            lines.push(format!("{device}: {}", names.join(" ")));
+        }
+    }
+
+    rng.shuffle(&mut lines);
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Counts up through names of three letters (`aaa` to `zzz`), then four
+/// letters, and so on, so every name is different however many are needed
+fn device_name(n: usize) -> String {
+    let (mut n, mut width) = (n, 3);
+    while n >= 26_usize.pow(width) {
+        n -= 26_usize.pow(width);
+        width += 1;
+    }
+    (0..width)
+        .rev()
+        .map(|place| (b'a' + (n / 26_usize.pow(place) % 26) as u8) as char)
+        .collect()
+}
+
+/// Six presents that each fit in a 3x3 grid, then regions packed anywhere from
+/// loosely to more than full
+pub fn day12(rng: &mut Rng, scale: usize) -> String {
+    let (mut out, areas) = presents(rng);
+    for _ in 0..1000 * scale {
+        out.push_str(&region(rng, &areas, 35..51));
+    }
+    out
+}
+
+/// Six presents that each fit in a 3x3 grid, grown one cell at a time so
+/// they're connected; returns them along with their areas
+pub fn presents(rng: &mut Rng) -> (String, Vec<usize>) {
+    let mut out = String::new();
+    let mut areas = vec![];
+    for index in 0..6 {
+        let mut grid = [[false; 3]; 3];
+        grid[1][1] = true;
+        let size = rng.range(5..8);
+        let mut filled = 1;
+        while filled < size {
+            let (r, c) = (rng.range(0..3), rng.range(0..3));
+            let touches = [
+                (r + 1, c),
+                (r.wrapping_sub(1), c),
+                (r, c + 1),
+                (r, c.wrapping_sub(1)),
+            ]
+            .iter()
+            .any(|(nr, nc)| *nr < 3 && *nc < 3 && grid[*nr][*nc]);
+            if !grid[r][c] && touches {
+                grid[r][c] = true;
+                filled += 1;
+            }
+        }
+        areas.push(size);
+
+        out.push_str(&format!("{index}:\n"));
+        for row in grid {
+            let row: String = row.iter().map(|f| if *f { '#' } else { '.' }).collect();
+            out.push_str(&format!("{row}\n"));
+        }
+        out.push('\n');
+    }
+    (out, areas)
+}
+
+/// A region's line, with each side in `sides` and presents (of the given
+/// areas) taking up anywhere from 60% to 105% of it
+pub fn region(rng: &mut Rng, areas: &[usize], sides: Range<usize>) -> String {
+    let (width, height) = (rng.range(sides.clone()), rng.range(sides));
+    let budget = width * height * rng.range(60..106) / 100;
+    let mut counts = vec![0; areas.len()];
+    let mut used = 0;
+    loop {
+        let shape = rng.range(0..areas.len());
+        if used + areas[shape] > budget {
+            break;
+        }
+        used += areas[shape];
+        counts[shape] += 1;
+    }
+    let counts: Vec<String> = counts.iter().map(|c| c.to_string()).collect();
+    format!("{width}x{height}: {}\n", counts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    type Generator = fn(&mut Rng, usize) -> String;
+    const GENERATORS: [Generator; 4] = [day09, day10, day11, day12];
+
+    #[test]
+    fn test_seeds() {
+        for generate in GENERATORS {
+            let first = generate(&mut Rng::new(7), 1);
+            assert_eq!(first, generate(&mut Rng::new(7), 1), "same seed");
+            assert_ne!(first, generate(&mut Rng::new(8), 1), "different seed");
+        }
+    }
+
+    #[test]
+    fn test_day11_names() {
+        assert_eq!("aaa", device_name(0));
+        assert_eq!("zzz", device_name(26 * 26 * 26 - 1));
+        assert_eq!("aaaa", device_name(26 * 26 * 26));
+
+        // more devices than there are three letter names
+        let devices = day11(&mut Rng::new(7), 40);
+        let mut defined = HashSet::new();
+        for line in devices.lines() {
+            let (name, _) = line.split_once(": ").unwrap();
+            assert!(defined.insert(name), "{name} is defined twice");
+        }
+        assert!(defined.len() > 26 * 26 * 26);
+        assert!(!defined.contains("out"));
+    }
+
+    #[test]
+    fn test_day09_corners() {
+        for seed in 0..20 {
+            let corners: Vec<(usize, usize)> = day09(&mut Rng::new(seed), 1)
+                .lines()
+                .map(|line| {
+                    let (x, y) = line.split_once(",").unwrap();
+                    (x.parse().unwrap(), y.parse().unwrap())
+                })
+                .collect();
+            let n = corners.len();
+            for i in 0..n {
+                let (prev, corner, next) =
+                    (corners[(i + n - 1) % n], corners[i], corners[(i + 1) % n]);
+                assert!(
+                    corner.0 == next.0 || corner.1 == next.1,
+                    "lined up with the next"
+                );
+                assert!(
+                    (prev.0 == corner.0) != (corner.0 == next.0),
+                    "a corner, not a straight run"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_day11_layers() {
+        let data = day11(&mut Rng::new(3), 2);
+        for name in ["svr", "you", "dac", "fft"] {
+            assert!(
+                data.contains(&format!("\n{name}: ")) || data.starts_with(&format!("{name}: "))
+            );
+        }
+        for line in data.lines() {
+            let (_, outputs) = line.split_once(": ").unwrap();
+            assert!(!outputs.is_empty(), "{line} has outputs");
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::{self, Read};
+use std::{fmt, fs, path::PathBuf};
+
+/// Where one puzzle input comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Input::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+            Input::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "stdin"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The inputs named on the command line, in order. `-` is stdin, and a path
+/// with `*`, `?` or `[` in it (that isn't itself a file) is a glob, expanded
+/// in sorted order for when the shell didn't. A glob that matches nothing is
+/// an error, since it's almost certainly a typo.
+pub fn expand(args: &[PathBuf]) -> Result<Vec<Input>, String> {
+    let mut inputs = vec![];
+    for arg in args {
+        let text = arg.to_string_lossy();
+        if text == "-" {
+            inputs.push(Input::Stdin);
+            continue;
+        }
+        if !text.contains(['*', '?', '[']) || arg.exists() {
+            inputs.push(Input::File(arg.clone()));
+            continue;
+        }
+
+        let paths = glob::glob(&text).map_err(|e| format!("Bad pattern {text}: {e}"))?;
+        let mut matched: Vec<PathBuf> = paths.filter_map(Result::ok).collect();
+        if matched.is_empty() {
+            return Err(format!("Nothing matches {text}"));
+        }
+        matched.sort();
+        inputs.extend(matched.into_iter().map(Input::File));
+    }
+    Ok(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["test3.txt", "test.txt", "test2.txt", "input.txt"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+
+        let args = [PathBuf::from("-"), dir.join("test*.txt")];
+        let expected = vec![
+            Input::Stdin,
+            Input::File(dir.join("test.txt")),
+            Input::File(dir.join("test2.txt")),
+            Input::File(dir.join("test3.txt")),
+        ];
+        assert_eq!(Ok(expected), expand(&args));
+        assert_eq!(
+            Ok(vec![Input::File(dir.join("missing.txt"))]),
+            expand(&[dir.join("missing.txt")]),
+            "plain paths are left for reading to fail on"
+        );
+        assert!(expand(&[dir.join("nope*.txt")]).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,8 @@
+// What the days have in common
+
+pub mod day;
+pub mod generate;
+pub mod input;
+pub mod parse;
+pub mod report;
+pub mod rng;
@@ -0,0 +1,106 @@
+use aoc::generate;
+use aoc::report::Format;
+use aoc::rng::Rng;
+use clap::{Parser, Subcommand};
+use std::{fs, path::PathBuf};
+
+mod runner;
+
+/// AOC 25 tools that work across days
+#[derive(Parser)]
+#[command()]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a random (but valid) puzzle input for a day
+    Gen {
+        /// Which day: 9, 10, 11 or 12
+        day: u8,
+
+        /// How big to make it; 1 is about the size of a real input
+        #[arg(long, default_value_t = 1)]
+        scale: usize,
+
+        /// The same seed always gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Write the input here instead of to stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Run days on their inputs, one after another
+    Run {
+        /// Which days (defaults to all of them)
+        days: Vec<u8>,
+
+        /// The input file to use, in each day's directory
+        #[arg(long, default_value = "input.txt")]
+        input: String,
+
+        /// How to write out the answers
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Gen {
+            day,
+            scale,
+            seed,
+            output,
+        } => {
+            let mut rng = Rng::new(seed);
+            let scale = scale.max(1);
+            let input = match day {
+                9 => generate::day09(&mut rng, scale),
+                10 => generate::day10(&mut rng, scale),
+                11 => generate::day11(&mut rng, scale),
+                12 => generate::day12(&mut rng, scale),
+                _ => {
+                    eprintln!("No generator for day {day}; try 9, 10, 11 or 12");
+                    std::process::exit(1);
+                }
+            };
+
+            match output {
+                Some(path) => {
+                    if let Err(e) = fs::write(&path, input) {
+                        eprintln!("Failed to write {}: {e}", path.display());
+                        std::process::exit(1);
+                    }
+                }
+                None => print!("{input}"),
+            }
+        }
+        Command::Run {
+            days,
+            input,
+            format,
+        } => {
+            let days = if days.is_empty() {
+                match runner::all_days() {
+                    Ok(days) => days,
+                    Err(e) => {
+                        eprintln!("Failed to find the days: {e}");
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                days
+            };
+            if !runner::run(&days, &input, format) {
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a puzzle input couldn't be parsed, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based, or 0 when it's about the input as a whole
+    pub line: usize,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// There was nothing to parse
+    Empty,
+    /// Something that should have been there wasn't
+    Missing(&'static str),
+    /// Text that should have been a number
+    BadNumber(String),
+    /// A character with no business being there
+    UnexpectedChar(char),
+    /// Well-formed, but doesn't make sense
+    Invalid(String),
+}
+
+impl ParseError {
+    pub fn new(line: usize, kind: ErrorKind) -> ParseError {
+        ParseError { line, kind }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}: ", self.line)?;
+        }
+        match &self.kind {
+            ErrorKind::Empty => write!(f, "nothing to parse"),
+            ErrorKind::Missing(what) => write!(f, "missing {what}"),
+            ErrorKind::BadNumber(text) => write!(f, "{text:?} isn't a number"),
+            ErrorKind::UnexpectedChar(c) => write!(f, "unexpected {c:?}"),
+            ErrorKind::Invalid(why) => write!(f, "{why}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text` as a number, blaming `line` if it isn't one
+pub fn number<T: FromStr>(line: usize, text: &str) -> Result<T, ParseError> {
+    text.trim()
+        .parse()
+        .map_err(|_| ParseError::new(line, ErrorKind::BadNumber(text.to_string())))
+}
+
+/// The next field, or an error saying that `what` is missing
+pub fn next_field<'a>(
+    line: usize,
+    fields: &mut impl Iterator<Item = &'a str>,
+    what: &'static str,
+) -> Result<&'a str, ParseError> {
+    fields
+        .next()
+        .ok_or(ParseError::new(line, ErrorKind::Missing(what)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        assert_eq!(
+            "line 3: \"1x\" isn't a number",
+            number::<usize>(3, "1x").unwrap_err().to_string()
+        );
+        let mut fields = "a".split(',');
+        assert_eq!(Ok("a"), next_field(1, &mut fields, "x"));
+        assert_eq!(
+            "line 1: missing x",
+            next_field(1, &mut fields, "x").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "nothing to parse",
+            ParseError::new(0, ErrorKind::Empty).to_string()
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use crate::input::Input;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+
+/// How a day writes out what it found
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Format {
+    /// For reading: whatever the day has to say, then its answers
+    Text,
+    /// A single JSON object on stdout once every input is done
+    Json,
+}
+
+/// Everything a day found, across all its inputs
+#[derive(Debug, Serialize)]
+pub struct Run {
+    pub day: u8,
+    pub inputs: Vec<Report>,
+    /// Why nothing could be solved at all, e.g. a glob that matched nothing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip)]
+    format: Format,
+}
+
+/// What one input came to
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub input: String,
+    pub answers: Vec<Answer>,
+    /// Whatever the day can say about each part of its input, e.g. day 12's
+    /// verdict for each region
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<serde_json::Value>,
+    /// Anything odd about the input that didn't stop it being solved, e.g.
+    /// day 11's devices that can't be reached
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip)]
+    format: Format,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Answer {
+    pub part: u8,
+    /// As a string, since some answers are too big for a JSON number
+    pub answer: String,
+    /// Parts that are worked out together share the time they took
+    pub seconds: f64,
+}
+
+impl Run {
+    pub fn new(day: u8, format: Format) -> Run {
+        Run {
+            day,
+            inputs: vec![],
+            error: None,
+            format,
+        }
+    }
+
+    /// Starts the report for the next input, with a heading if there's more
+    /// than one
+    pub fn start(&mut self, input: &Input, several: bool) -> &mut Report {
+        if several && self.format == Format::Text {
+            println!("== {input}");
+        }
+        self.inputs.push(Report {
+            input: input.to_string(),
+            answers: vec![],
+            details: vec![],
+            warnings: vec![],
+            error: None,
+            format: self.format,
+        });
+        self.inputs.last_mut().unwrap()
+    }
+
+    /// Gives up before solving anything
+    pub fn abort(mut self, error: impl fmt::Display) -> ! {
+        match self.format {
+            Format::Text => eprintln!("{error}"),
+            Format::Json => {
+                self.error = Some(error.to_string());
+                println!("{}", self.to_json());
+            }
+        }
+        std::process::exit(1);
+    }
+
+    /// Writes out the JSON if that's what was asked for, and exits with 1 if
+    /// any input failed
+    pub fn finish(self) {
+        if self.format == Format::Json {
+            println!("{}", self.to_json());
+        }
+        if self.inputs.iter().any(|report| report.error.is_some()) {
+            std::process::exit(1);
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a run is plain data")
+    }
+}
+
+impl Report {
+    pub fn is_text(&self) -> bool {
+        self.format == Format::Text
+    }
+
+    /// Prints a line of text output; JSON only has answers and details
+    pub fn say(&self, line: impl fmt::Display) {
+        if self.is_text() {
+            println!("{line}");
+        }
+    }
+
+    /// Prints the answer on a line of its own, as text, and records it
+    pub fn answer(&mut self, part: u8, answer: impl fmt::Display, elapsed: Duration) {
+        self.say(&answer);
+        self.answers.push(Answer {
+            part,
+            answer: answer.to_string(),
+            seconds: elapsed.as_secs_f64(),
+        });
+    }
+
+    /// Only goes in the JSON
+    pub fn detail(&mut self, detail: impl Serialize) {
+        if !self.is_text() {
+            let detail = serde_json::to_value(detail).expect("details are plain data");
+            self.details.push(detail);
+        }
+    }
+
+    /// Goes to stderr as text, so it doesn't get mixed up with the answers
+    pub fn warn(&mut self, warning: impl fmt::Display) {
+        if self.is_text() {
+            eprintln!("warning: {warning}");
+        }
+        self.warnings.push(warning.to_string());
+    }
+
+    /// Gives up on this input, but not the rest
+    pub fn fail(&mut self, error: impl fmt::Display) {
+        if self.is_text() {
+            eprintln!("{error}");
+        }
+        self.error = Some(error.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_json() {
+        let mut run = Run::new(5, Format::Json);
+        let report = run.start(&Input::File(PathBuf::from("test.txt")), true);
+        report.answer(2, 14, Duration::from_millis(1500));
+        report.detail(("3-5", 3));
+        report.warn("3-5 overlaps 4-6");
+        run.start(&Input::Stdin, true)
+            .fail("line 1: nothing to parse");
+
+        let json: serde_json::Value = serde_json::from_str(&run.to_json()).unwrap();
+        let expected = serde_json::json!({
+            "day": 5,
+            "inputs": [
+                {
+                    "input": "test.txt",
+                    "answers": [{ "part": 2, "answer": "14", "seconds": 1.5 }],
+                    "details": [["3-5", 3]],
+                    "warnings": ["3-5 overlaps 4-6"],
+                },
+                {
+                    "input": "stdin",
+                    "answers": [],
+                    "error": "line 1: nothing to parse",
+                },
+            ],
+        });
+        assert_eq!(expected, json);
+    }
+}
@@ -0,0 +1,84 @@
+// Days are separate crates that depend on this one, so the runner can't call
+// them directly; it goes through cargo, which also builds them if need be.
+
+use aoc::report::Format;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{fs, io};
+
+/// Where the days live: next to this crate
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the aoc crate is inside the repository")
+}
+
+/// Every day that has a directory, in order
+pub fn all_days() -> io::Result<Vec<u8>> {
+    let mut days: Vec<u8> = fs::read_dir(root())?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("Cargo.toml").exists())
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+    days.sort();
+    Ok(days)
+}
+
+fn command(day: u8, input: &str, format: &str) -> Command {
+    let dir: PathBuf = root().join(format!("{day:02}"));
+    let mut command = Command::new("cargo");
+    command
+        .args(["run", "--release", "--quiet", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .arg("--")
+        .arg(dir.join(input))
+        .args(["--format", format]);
+    command
+}
+
+/// Runs each day on its `input` in turn, returning whether they all worked.
+/// As JSON, it's one object with every day's own JSON in `days`; a day that
+/// didn't write any (because it didn't build, say) gets an `error` instead.
+pub fn run(days: &[u8], input: &str, format: Format) -> bool {
+    let mut ok = true;
+    match format {
+        Format::Text => {
+            for day in days {
+                println!("== day {day:02}");
+                match command(*day, input, "text").status() {
+                    Ok(status) => ok &= status.success(),
+                    Err(e) => {
+                        eprintln!("Failed to run cargo: {e}");
+                        return false;
+                    }
+                }
+            }
+        }
+        Format::Json => {
+            let mut results = vec![];
+            for day in days {
+                let output = command(*day, input, "json")
+                    .stderr(Stdio::inherit())
+                    .output();
+                let result = match output {
+                    Ok(output) => {
+                        ok &= output.status.success();
+                        serde_json::from_slice(&output.stdout).unwrap_or_else(|_| {
+                            let error = format!("day {day:02} didn't write any JSON");
+                            json!({ "day": day, "error": error })
+                        })
+                    }
+                    Err(e) => {
+                        ok = false;
+                        json!({ "day": day, "error": format!("Failed to run cargo: {e}") })
+                    }
+                };
+                results.push(result);
+            }
+            let results: Value = json!({ "days": results });
+            println!("{results:#}");
+        }
+    }
+    ok
+}
@@ -10,6 +10,7 @@ fi
 
 day="$1"
 
+NEWLIB="./src/lib.rs"
 NEWMAIN="./src/main.rs"
 TESTTXT="./test.txt"
 INPUTTXT="./input.txt"
@@ -21,8 +22,12 @@ cd "$day"
 touch "$TESTTXT"
 touch "$INPUTTXT"
 cargo add clap --features derive
+cargo add aoc --path ../aoc
+cargo add --dev proptest
 
-cp ../template/template.rs "$NEWMAIN"
-sed -i ""  "s/NEWDAY/$day/g" "$NEWMAIN"
+cp ../template/lib.rs "$NEWLIB"
+cp ../template/main.rs "$NEWMAIN"
+cp -R ../template/fuzz ./fuzz
+sed -i ""  "s/NEWDAY/$day/g" "$NEWLIB" "$NEWMAIN" ./fuzz/Cargo.toml ./fuzz/fuzz_targets/parse.rs
 
-vim "$NEWMAIN" "$TESTTXT" "$INPUTTXT"
+vim "$NEWLIB" "$TESTTXT" "$INPUTTXT"
@@ -0,0 +1,38 @@
+use aoc::day;
+use aoc::parse::{ErrorKind, ParseError};
+use aoc::report::{Format, Report};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// AOC 25 day NEWDAY
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Paths to the input files, each solved in turn; `-` reads stdin, and
+    /// globs like `test*.txt` are expanded
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// How to write out the answers
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+pub fn run() {
+    let args = Args::parse();
+    day::run(NEWDAY, &args.inputs, args.format, None, parse, process);
+}
+
+/// Has to return an error, never panic, whatever it's given; the fuzz target
+/// checks that
+pub fn parse(data: &str) -> Result<Vec<String>, ParseError> {
+    if data.is_empty() {
+        return Err(ParseError::new(0, ErrorKind::Empty));
+    }
+    Ok(data.lines().map(|line| line.to_string()).collect())
+}
+
+fn process(lines: Vec<String>, report: &mut Report) {
+    let start = Instant::now();
+    report.answer(1, lines.len(), start.elapsed());
+}
@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+set -e
+set -u
+set -o pipefail
+
+# Runs every day's tests. Property tests try PROPTEST_CASES random inputs
+# each (256 by default); set it higher for a longer soak, e.g.
+#   PROPTEST_CASES=10000 ./test.sh
+
+for day in [0-9][0-9] aoc; do
+	echo "== $day" >&2
+	(cd "$day" && cargo test --quiet)
+done